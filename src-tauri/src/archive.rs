@@ -0,0 +1,270 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::fs::{relative_path_string, sanitize_relative_path, unique_destination, AppState, ILLEGAL_NAME_CHARS};
+
+// Extracts every file in the archive under `dest_dir`. All entry names are
+// validated before anything is written so a single malicious entry aborts
+// the whole import instead of leaving a partial tree behind.
+fn extract_archive(zip_path: &Path, base_path: &Path, dest_rel: &Path) -> Result<Vec<String>, String> {
+    let file = fs::File::open(zip_path)
+        .map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    let dest_dir = base_path.join(dest_rel);
+    let mut planned: Vec<(usize, PathBuf, bool)> = Vec::new();
+
+    for index in 0..archive.len() {
+        let entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        let entry_rel = sanitize_relative_path(entry.name())
+            .map_err(|e| format!("Unsafe archive entry: {}", e))?;
+
+        if entry_rel.as_os_str().is_empty() {
+            continue;
+        }
+
+        if !entry.is_dir() && dest_dir.join(&entry_rel).exists() {
+            return Err(format!("Destination '{}' already exists", dest_rel.join(&entry_rel).display()));
+        }
+
+        planned.push((index, entry_rel, entry.is_dir()));
+    }
+
+    let mut imported = Vec::new();
+
+    for (index, entry_rel, is_dir) in planned {
+        let out_path = dest_dir.join(&entry_rel);
+
+        if is_dir {
+            fs::create_dir_all(&out_path)
+                .map_err(|e| format!("Failed to create folder: {}", e))?;
+            continue;
+        }
+
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create parent directories: {}", e))?;
+        }
+
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
+        let mut out_file = fs::File::create(&out_path)
+            .map_err(|e| format!("Failed to create file: {}", e))?;
+        io::copy(&mut entry, &mut out_file)
+            .map_err(|e| format!("Failed to extract file: {}", e))?;
+
+        imported.push(dest_rel.join(&entry_rel).to_string_lossy().to_string());
+    }
+
+    Ok(imported)
+}
+
+#[tauri::command]
+pub async fn import_archive(app_handle: tauri::AppHandle, zip_path: String, dest_rel: String) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let archive_path = PathBuf::from(&zip_path);
+    if !archive_path.is_file() {
+        return Err(format!("Archive '{}' does not exist", zip_path));
+    }
+
+    let dest = sanitize_relative_path(&dest_rel)?;
+
+    extract_archive(&archive_path, base_path, &dest)
+}
+
+// Plain-text extensions imported as notes.
+const NOTE_IMPORT_EXTENSIONS: [&str; 2] = ["txt", "markdown"];
+
+// Replaces characters the vault can't hold in a name. Names that end up
+// blank become "untitled".
+fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| if ILLEGAL_NAME_CHARS.contains(&c) || c.is_control() { '-' } else { c })
+        .collect();
+    let cleaned = cleaned.trim().trim_end_matches('.');
+
+    if cleaned.is_empty() {
+        "untitled".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+// Name for an imported file, with `.txt`/`.markdown` turned into `.md`.
+fn imported_file_name(name: &str) -> String {
+    let name = sanitize_file_name(name);
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && NOTE_IMPORT_EXTENSIONS.contains(&ext.to_lowercase().as_str()) => {
+            format!("{}.md", stem)
+        }
+        _ => name,
+    }
+}
+
+// Copies `source` into `dest`, recursing into folders. Hidden entries and
+// symlinks are skipped; files that fail to copy are logged and skipped so
+// one unreadable file doesn't abort the import.
+fn copy_tree(base_path: &Path, source: &Path, dest: &Path, imported: &mut Vec<String>) -> Result<(), String> {
+    fs::create_dir_all(dest)
+        .map_err(|e| format!("Failed to create folder: {}", e))?;
+
+    let mut entries: Vec<fs::DirEntry> = fs::read_dir(source)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to read entry: {}", e))?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+
+        let file_type = entry.file_type()
+            .map_err(|e| format!("Failed to read metadata: {}", e))?;
+
+        if file_type.is_dir() {
+            copy_tree(base_path, &entry.path(), &dest.join(sanitize_file_name(&name)), imported)?;
+        } else if file_type.is_file() {
+            let out_path = unique_destination(dest, &imported_file_name(&name));
+            match fs::copy(entry.path(), &out_path) {
+                Ok(_) => imported.push(relative_path_string(base_path, &out_path)?),
+                Err(e) => log::warn!("Skipping '{}' during import: {}", entry.path().display(), e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Copies an external folder tree into `dest_rel`, turning plain-text files
+// into notes. Existing files are never overwritten; clashing names get a
+// numeric suffix. Returns the vault-relative paths that were written.
+#[tauri::command]
+pub async fn import_directory(app_handle: tauri::AppHandle, external_path: String, dest_rel: String) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    import_tree(base_path, Path::new(&external_path), &dest_rel)
+}
+
+fn import_tree(base_path: &Path, source: &Path, dest_rel: &str) -> Result<Vec<String>, String> {
+    if !source.is_dir() {
+        return Err(format!("Folder '{}' does not exist", source.display()));
+    }
+
+    let dest = sanitize_relative_path(dest_rel)?;
+    let dest_path = base_path.join(&dest);
+    if dest_path.exists() && !dest_path.is_dir() {
+        return Err(format!("'{}' is not a folder", dest_rel));
+    }
+
+    // Importing a folder into itself would copy forever
+    let source_canonical = source.canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+    let base_canonical = base_path.canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+    if base_canonical.join(&dest).starts_with(&source_canonical) {
+        return Err("Cannot import a folder into itself".to_string());
+    }
+
+    let mut imported = Vec::new();
+    copy_tree(base_path, source, &dest_path, &mut imported)?;
+
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+        let mut writer = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, content) in entries {
+            writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn extracts_under_destination() {
+        let outside = TempVault::new();
+        let vault = TempVault::new();
+        let zip_path = outside.path().join("bundle.zip");
+        write_zip(&zip_path, &[("a.md", "a"), ("sub/b.md", "b")]);
+
+        let mut imported = extract_archive(&zip_path, vault.path(), Path::new("Imported")).unwrap();
+        imported.sort();
+
+        assert_eq!(imported, vec!["Imported/a.md", "Imported/sub/b.md"]);
+        assert_eq!(fs::read_to_string(vault.path().join("Imported/sub/b.md")).unwrap(), "b");
+    }
+
+    #[test]
+    fn rejects_zip_slip_entries() {
+        let outside = TempVault::new();
+        let vault = TempVault::new();
+        let dest = vault.path().join("Imported");
+
+        for evil in ["../evil.md", "../../evil.md", "sub/../../evil.md", "/evil.md"] {
+            let zip_path = outside.path().join("evil.zip");
+            write_zip(&zip_path, &[("good.md", "fine"), (evil, "pwned")]);
+
+            assert!(extract_archive(&zip_path, vault.path(), Path::new("Imported")).is_err(), "accepted {}", evil);
+        }
+
+        // Validation runs first, so not even the safe entry was written
+        assert!(!dest.exists());
+        assert!(!vault.path().join("evil.md").exists());
+        assert!(!outside.path().join("evil.md").exists());
+        assert_eq!(fs::read_dir(vault.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn nested_directory_imports_with_text_files_as_notes() {
+        let outside = TempVault::new();
+        outside.write("Journal/2024/day.txt", "plain text");
+        outside.write("Journal/readme.markdown", "# Readme");
+        outside.write("Journal/a:b.md", "odd name");
+        outside.write("photo.png", "png");
+        outside.write(".git/config", "hidden");
+        let vault = TempVault::new();
+        vault.write("Imported/photo.png", "already here");
+
+        let mut imported = import_tree(vault.path(), outside.path(), "Imported").unwrap();
+        imported.sort();
+
+        assert_eq!(
+            imported,
+            vec!["Imported/Journal/2024/day.md", "Imported/Journal/a-b.md", "Imported/Journal/readme.md", "Imported/photo 1.png"]
+        );
+        assert_eq!(vault.read("Imported/Journal/2024/day.md"), "plain text");
+        assert_eq!(vault.read("Imported/photo.png"), "already here");
+
+        assert!(import_tree(vault.path(), &outside.path().join("Missing"), "Imported").is_err());
+        assert!(import_tree(vault.path(), outside.path(), "../Escape").is_err());
+    }
+}
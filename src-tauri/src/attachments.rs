@@ -0,0 +1,366 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use tauri::Manager;
+
+use crate::fs::{build_entry, collect_vault_files, is_markdown_file, normalize_lexically, relative_path_string, sanitize_relative_path, with_note_content, write_atomic, AppState, Entry, Link};
+
+// Default cap for `read_file_bytes` so a stray video doesn't get shipped
+// across IPC in one piece.
+const DEFAULT_MAX_READ_BYTES: u64 = 20 * 1024 * 1024;
+
+#[tauri::command]
+pub async fn read_file_bytes(app_handle: tauri::AppHandle, rel: String, max_bytes: Option<u64>) -> Result<Vec<u8>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    read_vault_bytes(base_path, &rel, max_bytes)
+}
+
+fn read_vault_bytes(base_path: &Path, rel: &str, max_bytes: Option<u64>) -> Result<Vec<u8>, String> {
+    let file_path = base_path.join(sanitize_relative_path(rel)?);
+
+    if !file_path.is_file() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    // Symlinks could otherwise point the read outside the vault
+    let canonical_base = base_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve vault path: {}", e))?;
+    let canonical_file = file_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve file path: {}", e))?;
+    if !canonical_file.starts_with(&canonical_base) {
+        return Err(format!("Path '{}' is outside the vault", rel));
+    }
+
+    let limit = max_bytes.unwrap_or(DEFAULT_MAX_READ_BYTES);
+    let len = fs::metadata(&file_path)
+        .map_err(|e| format!("Failed to read metadata: {}", e))?
+        .len();
+    if len > limit {
+        return Err(format!("File '{}' is {} bytes, over the {} byte limit", rel, len, limit));
+    }
+
+    fs::read(&file_path).map_err(|e| format!("Failed to read file: {}", e))
+}
+
+#[tauri::command]
+pub async fn write_file_bytes(app_handle: tauri::AppHandle, rel: String, bytes: Vec<u8>) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    write_vault_bytes(base_path, &rel, &bytes)
+}
+
+fn write_vault_bytes(base_path: &Path, rel: &str, bytes: &[u8]) -> Result<(), String> {
+    let file_rel = sanitize_relative_path(rel)?;
+    if file_rel.as_os_str().is_empty() {
+        return Err("A file path is required".to_string());
+    }
+
+    let file_path = base_path.join(&file_rel);
+
+    if file_path.is_dir() {
+        return Err(format!("Path '{}' is a folder", rel));
+    }
+
+    // Create parent directories if they don't exist
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directories: {}", e))?;
+    }
+
+    write_atomic(&file_path, bytes)
+        .map_err(|e| format!("Failed to write file: {}", e))
+}
+
+// A reference to a file from a note: `[[x]]`, `![[x]]`, `[text](x)` or
+// `![alt](x)`. Wikilink size hints (`|300`) end up as display text.
+pub(crate) struct FileReference {
+    pub(crate) link: Link,
+    pub(crate) is_embed: bool,
+}
+
+pub(crate) fn parse_file_references(content: &str, source_file: &str) -> Vec<FileReference> {
+    let wiki_regex = Regex::new(r"(!?)\[\[([^\[\]]+)\]\]").unwrap();
+    let markdown_regex = Regex::new(r#"(!?)\[([^\]]*)\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).unwrap();
+    let mut references = Vec::new();
+
+    for captures in wiki_regex.captures_iter(content) {
+        let full = captures.get(0).unwrap();
+        let mut parts = captures[2].splitn(2, '|');
+        let target = parts.next().unwrap_or("").trim().to_string();
+        let display_text = parts.next().map(|display| display.trim().to_string());
+
+        references.push(FileReference {
+            link: Link {
+                source_file: source_file.to_string(),
+                target_note: target,
+                display_text,
+                position: full.start(),
+                length: full.len(),
+            },
+            is_embed: !captures[1].is_empty(),
+        });
+    }
+
+    for captures in markdown_regex.captures_iter(content) {
+        let full = captures.get(0).unwrap();
+        let target = &captures[3];
+
+        if target.contains("://") || target.starts_with("mailto:") {
+            continue;
+        }
+
+        references.push(FileReference {
+            link: Link {
+                source_file: source_file.to_string(),
+                target_note: target.to_string(),
+                display_text: Some(captures[2].to_string()).filter(|alt| !alt.is_empty()),
+                position: full.start(),
+                length: full.len(),
+            },
+            is_embed: !captures[1].is_empty(),
+        });
+    }
+
+    references.sort_by_key(|reference| reference.link.position);
+    references
+}
+
+// Case-folded, forward-slash key for matching reference targets to files.
+fn path_key(path: &str) -> String {
+    path.replace('\\', "/").replace("%20", " ").to_lowercase()
+}
+
+// Every file in the vault, indexed by relative path and by file name so
+// references can be resolved the way the editor does.
+pub(crate) struct FileIndex {
+    pub(crate) files: Vec<String>,
+    by_path: HashMap<String, String>,
+    by_name: HashMap<String, Vec<String>>,
+}
+
+impl FileIndex {
+    pub(crate) fn build(base_path: &Path) -> Result<Self, String> {
+        let mut files = Vec::new();
+        let mut by_path = HashMap::new();
+        let mut by_name: HashMap<String, Vec<String>> = HashMap::new();
+
+        for path in collect_vault_files(base_path)? {
+            let rel = relative_path_string(base_path, &path)?;
+            let key = path_key(&rel);
+            let name = key.rsplit('/').next().unwrap_or(&key).to_string();
+
+            by_name.entry(name).or_default().push(rel.clone());
+            by_path.insert(key, rel.clone());
+            files.push(rel);
+        }
+
+        // Shallowest path first so an ambiguous name resolves predictably
+        for candidates in by_name.values_mut() {
+            candidates.sort_by(|a, b| {
+                let depth = |p: &str| p.matches(['/', '\\']).count();
+                depth(a).cmp(&depth(b)).then_with(|| a.cmp(b))
+            });
+        }
+
+        Ok(FileIndex { files, by_path, by_name })
+    }
+
+    // Paths are tried relative to the referencing note first, then to the
+    // vault root; bare names match by file name anywhere in the vault.
+    pub(crate) fn resolve(&self, source_file: &str, target: &str) -> Option<String> {
+        let target = target.split('#').next().unwrap_or(target).trim();
+        if target.is_empty() {
+            return None;
+        }
+
+        let target = path_key(target);
+
+        if target.contains('/') {
+            let source_dir = Path::new(source_file).parent().unwrap_or(Path::new(""));
+            let relative = normalize_lexically(&source_dir.join(&target));
+            let absolute = normalize_lexically(Path::new(target.trim_start_matches('/')));
+
+            return [relative, absolute]
+                .into_iter()
+                .flatten()
+                .find_map(|path| self.by_path.get(&path_key(&path.to_string_lossy())).cloned());
+        }
+
+        self.by_name.get(&target)?.first().cloned()
+    }
+
+    // Vault files that `note` embeds or links to.
+    pub(crate) fn referenced_by(&self, note: &str, content: &str) -> Vec<String> {
+        parse_file_references(content, note)
+            .into_iter()
+            .filter_map(|reference| self.resolve(note, &reference.link.target_note))
+            .collect()
+    }
+
+    // Non-markdown files missing from `referenced`.
+    pub(crate) fn unused_attachments<'a>(&'a self, referenced: &'a HashSet<String>) -> impl Iterator<Item = &'a String> + 'a {
+        self.files
+            .iter()
+            .filter(move |file| !is_markdown_file(Path::new(file)) && !referenced.contains(*file))
+    }
+}
+
+#[tauri::command]
+pub async fn find_attachment_references(app_handle: tauri::AppHandle, rel: String) -> Result<Vec<Link>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    attachment_references(base_path, &rel)
+}
+
+fn attachment_references(base_path: &Path, rel: &str) -> Result<Vec<Link>, String> {
+    let index = FileIndex::build(base_path)?;
+
+    let attachment = match index.resolve("", rel) {
+        Some(attachment) if path_key(&attachment) == path_key(rel) => attachment,
+        _ => return Err(format!("File '{}' does not exist", rel)),
+    };
+
+    let mut references = Vec::new();
+
+    for note in index.files.iter().filter(|file| is_markdown_file(Path::new(file))) {
+        let embeds = with_note_content(&base_path.join(note), |content| {
+            parse_file_references(content, note)
+                .into_iter()
+                .filter(|reference| reference.is_embed)
+                .map(|reference| reference.link)
+                .collect::<Vec<_>>()
+        })?;
+
+        references.extend(
+            embeds
+                .into_iter()
+                .filter(|link| index.resolve(note, &link.target_note).as_deref() == Some(attachment.as_str())),
+        );
+    }
+
+    Ok(references)
+}
+
+// Non-markdown files that no note embeds or links to. Hidden folders such
+// as `.trash` and app-managed files are never part of the report.
+#[tauri::command]
+pub async fn find_unused_attachments(app_handle: tauri::AppHandle) -> Result<Vec<Entry>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    collect_unused_attachments(base_path)
+}
+
+fn collect_unused_attachments(base_path: &Path) -> Result<Vec<Entry>, String> {
+    let index = FileIndex::build(base_path)?;
+    let mut referenced: HashSet<String> = HashSet::new();
+
+    for note in index.files.iter().filter(|file| is_markdown_file(Path::new(file))) {
+        referenced.extend(with_note_content(&base_path.join(note), |content| index.referenced_by(note, content))?);
+    }
+
+    let mut unused = Vec::new();
+
+    for file in index.unused_attachments(&referenced) {
+        let path = base_path.join(file);
+        let metadata = fs::metadata(&path)
+            .map_err(|e| format!("Failed to read metadata: {}", e))?;
+        unused.push(build_entry(base_path, &path, &metadata)?);
+    }
+
+    Ok(unused)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    // A 1x1 transparent PNG
+    const PNG: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+        0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f, 0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63,
+        0x00, 0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0d, 0x0a, 0x2d, 0xb4, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60,
+        0x82,
+    ];
+
+    #[test]
+    fn reads_a_png_byte_for_byte() {
+        let vault = TempVault::new();
+        vault.write("assets/pixel.png", PNG);
+
+        assert_eq!(read_vault_bytes(vault.path(), "assets/pixel.png", None).unwrap(), PNG);
+        assert!(read_vault_bytes(vault.path(), "assets/pixel.png", Some(10)).is_err());
+        assert!(read_vault_bytes(vault.path(), "../pixel.png", None).is_err());
+    }
+
+    #[test]
+    fn written_bytes_read_back_identically() {
+        let vault = TempVault::new();
+
+        write_vault_bytes(vault.path(), "pasted/nested/pixel.png", PNG).unwrap();
+        assert_eq!(read_vault_bytes(vault.path(), "pasted/nested/pixel.png", None).unwrap(), PNG);
+
+        assert!(write_vault_bytes(vault.path(), "../escape.png", PNG).is_err());
+        assert!(write_vault_bytes(vault.path(), "pasted", PNG).is_err());
+    }
+
+    #[test]
+    fn both_embed_syntaxes_reference_the_same_image() {
+        let vault = TempVault::new();
+        vault.write("assets/diagram.png", PNG);
+        vault.write("assets/other.png", PNG);
+        vault.write("Wiki.md", "![[diagram.png]] ![[other.png]]");
+        vault.write("Notes/Markdown.md", "![a diagram](../assets/diagram.png)");
+        vault.write("Plain.md", "[[diagram.png]]");
+
+        let references = attachment_references(vault.path(), "assets/diagram.png").unwrap();
+        let mut sources: Vec<&str> = references.iter().map(|link| link.source_file.as_str()).collect();
+        sources.sort();
+
+        // A plain link is not an embed
+        assert_eq!(sources, vec!["Notes/Markdown.md", "Wiki.md"]);
+    }
+
+    #[test]
+    fn only_stray_attachments_are_unused() {
+        let vault = TempVault::new();
+        vault.write("assets/embedded.png", PNG);
+        vault.write("assets/stray.png", PNG);
+        vault.write(".trash/deleted.png", PNG);
+        vault.write(".tau_ui_state.json", "{}");
+        vault.write("Note.md", "![[embedded.png]]");
+
+        let unused = collect_unused_attachments(vault.path()).unwrap();
+        let paths: Vec<&str> = unused.iter().map(|entry| entry.path.as_str()).collect();
+
+        assert_eq!(paths, vec!["assets/stray.png"]);
+    }
+}
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::fs::AppState;
+
+// How wikilink targets are looked up. `Global` matches by name anywhere in
+// the vault; `Relative` first tries the target as a path from the linking
+// note's folder, so `[[./sibling]]` and `[[../other/note]]` work.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkResolution {
+    #[default]
+    Global,
+    Relative,
+}
+
+// Line ending used when `normalize_on_write` rewrites a note.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+// Per-vault settings stored in `.tau_config.json`. Missing fields take their
+// defaults so older config files keep loading.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct VaultConfig {
+    pub link_resolution: LinkResolution,
+    // Tidy notes in write_note: trailing whitespace, line endings and the
+    // final newline. Off by default so notes are written verbatim.
+    pub normalize_on_write: bool,
+    pub line_ending: LineEnding,
+    // Words word_frequency leaves out; None uses the built-in English list
+    pub stopwords: Option<Vec<String>>,
+}
+
+fn get_config_path(base_path: &Path) -> PathBuf {
+    base_path.join(".tau_config.json")
+}
+
+pub(crate) fn read_vault_config(base_path: &Path) -> VaultConfig {
+    fs::read_to_string(get_config_path(base_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub async fn get_vault_config(app_handle: tauri::AppHandle) -> Result<VaultConfig, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    Ok(read_vault_config(base_path))
+}
+
+#[tauri::command]
+pub async fn set_vault_config(app_handle: tauri::AppHandle, config: VaultConfig) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let content = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+
+    fs::write(get_config_path(base_path), content)
+        .map_err(|e| format!("Failed to write config: {}", e))
+}
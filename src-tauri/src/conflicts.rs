@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::fs::{collect_markdown_files, relative_path_string, with_note_content, AppState};
+
+// One `<<<<<<<` .. `>>>>>>>` region, with 1-based inclusive line numbers.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConflictHit {
+    pub path: String,
+    pub start_line: usize,
+    pub separator_line: Option<usize>,
+    pub end_line: usize,
+}
+
+fn find_conflict_regions(content: &str, path: &str) -> Vec<ConflictHit> {
+    let mut hits = Vec::new();
+    let mut in_fence = false;
+    let mut open: Option<(usize, Option<usize>)> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim_start();
+
+        // Notes documenting git put example markers in code blocks
+        if open.is_none() && (trimmed.starts_with("```") || trimmed.starts_with("~~~")) {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        if line.starts_with("<<<<<<<") {
+            open = Some((line_number, None));
+        } else if line.starts_with("=======") {
+            if let Some((_, separator)) = open.as_mut() {
+                separator.get_or_insert(line_number);
+            }
+        } else if line.starts_with(">>>>>>>") {
+            if let Some((start_line, separator_line)) = open.take() {
+                hits.push(ConflictHit {
+                    path: path.to_string(),
+                    start_line,
+                    separator_line,
+                    end_line: line_number,
+                });
+            }
+        }
+    }
+
+    hits
+}
+
+#[tauri::command]
+pub async fn find_conflicts(app_handle: tauri::AppHandle) -> Result<Vec<ConflictHit>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let mut hits = Vec::new();
+
+    for path in collect_markdown_files(base_path)? {
+        let rel = relative_path_string(base_path, &path)?;
+        hits.extend(with_note_content(&path, |content| find_conflict_regions(content, &rel))?);
+    }
+
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fenced_markers_are_not_conflicts() {
+        let real = "Intro\n<<<<<<< HEAD\nmine\n=======\ntheirs\n>>>>>>> origin/main\nOutro";
+        let hits = find_conflict_regions(real, "Real.md");
+        let ranges: Vec<(&str, usize, Option<usize>, usize)> = hits
+            .iter()
+            .map(|hit| (hit.path.as_str(), hit.start_line, hit.separator_line, hit.end_line))
+            .collect();
+        assert_eq!(ranges, vec![("Real.md", 2, Some(4), 6)]);
+
+        let documented = "How a conflict looks:\n```\n<<<<<<< HEAD\nmine\n=======\ntheirs\n>>>>>>> branch\n```\n~~~\n<<<<<<< HEAD\n~~~";
+        assert!(find_conflict_regions(documented, "Guide.md").is_empty());
+    }
+}
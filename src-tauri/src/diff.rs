@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use std::fs;
+use tauri::Manager;
+
+use crate::fs::AppState;
+
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: String,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub content: String,
+    // Set on the last line of a side that doesn't end with a newline, so a
+    // diff that only touches the trailing newline is still explainable
+    pub missing_newline: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+// Line numbers are 1-based to match what the editor displays.
+fn diff_hunks(old: &str, new: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(old, new);
+    let mut hunks = Vec::new();
+
+    for group in diff.grouped_ops(CONTEXT_LINES) {
+        let (first, last) = match (group.first(), group.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => continue,
+        };
+
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+        let mut lines = Vec::new();
+
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let kind = match change.tag() {
+                    ChangeTag::Delete => "removed",
+                    ChangeTag::Insert => "added",
+                    ChangeTag::Equal => "context",
+                };
+
+                lines.push(DiffLine {
+                    kind: kind.to_string(),
+                    old_line: change.old_index().map(|i| i + 1),
+                    new_line: change.new_index().map(|i| i + 1),
+                    content: change.value().trim_end_matches(['\n', '\r']).to_string(),
+                    missing_newline: change.missing_newline(),
+                });
+            }
+        }
+
+        hunks.push(DiffHunk {
+            old_start: old_range.start + 1,
+            old_lines: old_range.len(),
+            new_start: new_range.start + 1,
+            new_lines: new_range.len(),
+            lines,
+        });
+    }
+
+    hunks
+}
+
+#[tauri::command]
+pub async fn note_diff(app_handle: tauri::AppHandle, rel: String, other_content: String) -> Result<Vec<DiffHunk>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let file_path = base_path.join(&rel);
+
+    if !file_path.exists() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    let current = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read note: {}", e))?;
+
+    Ok(diff_hunks(&current, &other_content))
+}
@@ -0,0 +1,409 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use tauri::Manager;
+
+use crate::frontmatter::{is_note_readonly, readonly_error, split_frontmatter};
+use crate::fs::{collect_markdown_files, glob_matches, is_ignored, parse_links_from_content, read_ignore_patterns, relative_path_string, sanitize_relative_path, with_note_content, write_atomic, AppState};
+use crate::graph::{reachable, LinkGraph};
+use crate::links::{collect_vault_links, note_link_path, note_stem, NoteIndex};
+use crate::outline::anchor_slug;
+
+// Wikilinks to notes inside the bundle become links to that note's section;
+// links leaving the bundle are kept as they are.
+fn rewrite_to_anchors(content: &str, source_file: &str, index: &NoteIndex, anchors: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut last_end = 0;
+
+    for link in parse_links_from_content(content, "") {
+        let anchor = match index.resolve_link_target(source_file, &link.target_note).and_then(|note| anchors.get(&note)) {
+            Some(anchor) => anchor,
+            None => continue,
+        };
+
+        // Embeds have no in-document equivalent
+        if content[..link.position].ends_with('!') {
+            continue;
+        }
+
+        let text = link.display_text.as_deref().unwrap_or(link.target_note.as_str());
+        output.push_str(&content[last_end..link.position]);
+        output.push_str(&format!("[{}](#{})", text, anchor));
+        last_end = link.position + link.length;
+    }
+
+    output.push_str(&content[last_end..]);
+    output
+}
+
+fn bundle_notes(base_path: &Path, rel: &str, max_depth: Option<usize>) -> Result<String, String> {
+    let graph = LinkGraph::build(base_path)?;
+
+    if !graph.notes.iter().any(|note| note == rel) {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    // reachable() already visits each note once, however many paths lead to it
+    let notes = reachable(&graph, rel, max_depth);
+    let index = NoteIndex::build(base_path)?;
+
+    let mut anchors: HashMap<String, String> = HashMap::new();
+    let mut used: HashSet<String> = HashSet::new();
+
+    for note in &notes {
+        let base_slug = anchor_slug(note_stem(note));
+        let mut slug = base_slug.clone();
+        let mut n = 1;
+        while !used.insert(slug.clone()) {
+            slug = format!("{}-{}", base_slug, n);
+            n += 1;
+        }
+        anchors.insert(note.clone(), slug);
+    }
+
+    let mut sections = Vec::new();
+
+    for note in &notes {
+        let section = with_note_content(&base_path.join(note), |content| {
+            let body = split_frontmatter(content).map(|(_, body)| body).unwrap_or(content);
+            format!(
+                "# {}\n\n{}",
+                note_stem(note),
+                rewrite_to_anchors(body, note, &index, &anchors).trim()
+            )
+        })?;
+        sections.push(section);
+    }
+
+    Ok(sections.join("\n\n") + "\n")
+}
+
+#[tauri::command]
+pub async fn export_bundle(app_handle: tauri::AppHandle, rel: String, max_depth: Option<usize>) -> Result<String, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    bundle_notes(base_path, &rel, max_depth)
+}
+
+// One row of `export_links`. `target` is the link as written; `resolved_path`
+// is the note it points at, when it resolves.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedLink {
+    pub source: String,
+    pub target: String,
+    pub display: Option<String>,
+    pub resolved: bool,
+    pub resolved_path: Option<String>,
+}
+
+// Quotes a CSV field when it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn links_to_csv(links: &[ExportedLink]) -> String {
+    let mut csv = String::from("source,target,display,resolved,resolved_path\n");
+
+    for link in links {
+        let row = [
+            csv_field(&link.source),
+            csv_field(&link.target),
+            csv_field(link.display.as_deref().unwrap_or("")),
+            link.resolved.to_string(),
+            csv_field(link.resolved_path.as_deref().unwrap_or("")),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+// Writes every wikilink in the vault to `dest_path` (outside or inside the
+// vault) as `csv` or `json`, for analysis in graph tools or spreadsheets.
+#[tauri::command]
+pub async fn export_links(app_handle: tauri::AppHandle, format: String, dest_path: String) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    write_link_export(base_path, &format, Path::new(&dest_path))
+}
+
+fn write_link_export(base_path: &Path, format: &str, dest: &Path) -> Result<(), String> {
+    let format = format.to_lowercase();
+    if format != "csv" && format != "json" {
+        return Err(format!("Unsupported export format '{}', expected 'csv' or 'json'", format));
+    }
+
+    if dest.is_dir() {
+        return Err(format!("Destination '{}' is a directory", dest.display()));
+    }
+    match dest.parent() {
+        Some(parent) if parent.as_os_str().is_empty() || parent.is_dir() => {}
+        _ => return Err(format!("Destination folder for '{}' does not exist", dest.display())),
+    }
+
+    let index = NoteIndex::build(base_path)?;
+    let links: Vec<ExportedLink> = collect_vault_links(base_path, &index)?
+        .into_iter()
+        .map(|link| {
+            let resolved_path = index.resolve_link_target(&link.source_file, &link.target_note);
+            ExportedLink {
+                resolved: resolved_path.is_some(),
+                resolved_path,
+                source: link.source_file,
+                target: link.target_note,
+                display: link.display_text,
+            }
+        })
+        .collect();
+
+    let content = if format == "csv" {
+        links_to_csv(&links)
+    } else {
+        serde_json::to_string_pretty(&links)
+            .map_err(|e| format!("Failed to serialize links: {}", e))?
+    };
+
+    fs::write(dest, content)
+        .map_err(|e| format!("Failed to write export: {}", e))
+}
+
+// Concatenates every note matching `glob` (e.g. `journal/2024-06-1?.md`) into
+// `dest_rel`, sorted by path, each under a heading with its file name and
+// without its frontmatter. `separator` goes between the sections. Source
+// notes are left untouched; an existing rollup is overwritten.
+fn rollup_matching(base_path: &Path, glob: &str, dest_rel: &str, separator: &str) -> Result<(), String> {
+    let dest = sanitize_relative_path(dest_rel)?;
+    let dest_path = base_path.join(&dest);
+
+    if is_note_readonly(&dest_path) {
+        return Err(readonly_error(dest_rel));
+    }
+
+    let mut sections = Vec::new();
+
+    for path in collect_markdown_files(base_path)? {
+        let rel = relative_path_string(base_path, &path)?;
+        if path == dest_path || !glob_matches(glob, &rel) {
+            continue;
+        }
+
+        let section = with_note_content(&path, |content| {
+            let body = split_frontmatter(content).map(|(_, body)| body).unwrap_or(content);
+            format!("# {}\n\n{}\n", note_stem(&rel), body.trim())
+        })?;
+        sections.push(section);
+    }
+
+    if sections.is_empty() {
+        return Err(format!("No notes match '{}'", glob));
+    }
+
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directories: {}", e))?;
+    }
+
+    write_atomic(&dest_path, sections.join(separator).as_bytes())
+        .map_err(|e| format!("Failed to write rollup: {}", e))
+}
+
+#[tauri::command]
+pub async fn rollup_notes(app_handle: tauri::AppHandle, glob: String, dest_rel: String, separator: String) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    rollup_matching(base_path, &glob, &dest_rel, &separator)
+}
+
+// One folder of the vault map built by `generate_index`.
+#[derive(Default)]
+struct IndexFolder {
+    folders: BTreeMap<String, IndexFolder>,
+    notes: Vec<String>,
+}
+
+// Nested bullet list of `folder`, subfolders before notes like the sidebar,
+// indented two spaces per level.
+fn render_index(folder: &IndexFolder, depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth);
+
+    for (name, child) in &folder.folders {
+        output.push_str(&format!("{}- {}/\n", indent, name));
+        render_index(child, depth + 1, output);
+    }
+
+    for note in &folder.notes {
+        output.push_str(&format!("{}- [[{}|{}]]\n", indent, note_link_path(note), note_stem(note)));
+    }
+}
+
+// Writes `dest_rel` as a map of the vault: the folder tree as nested bullets
+// with a link to every note. Notes matched by `.tauignore` are left out.
+// Re-running regenerates the whole note.
+#[tauri::command]
+pub async fn generate_index(app_handle: tauri::AppHandle, dest_rel: String) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    write_vault_index(base_path, &dest_rel)
+}
+
+fn write_vault_index(base_path: &Path, dest_rel: &str) -> Result<(), String> {
+    let dest = sanitize_relative_path(dest_rel)?;
+    let dest_path = base_path.join(&dest);
+
+    if is_note_readonly(&dest_path) {
+        return Err(readonly_error(dest_rel));
+    }
+
+    let ignored = read_ignore_patterns(base_path);
+    let mut root = IndexFolder::default();
+
+    for path in collect_markdown_files(base_path)? {
+        let rel = relative_path_string(base_path, &path)?;
+        if path == dest_path || is_ignored(&ignored, &rel) {
+            continue;
+        }
+
+        let mut folder = &mut root;
+        let mut parts: Vec<&str> = rel.split('/').collect();
+        parts.pop();
+        for part in parts {
+            folder = folder.folders.entry(part.to_string()).or_default();
+        }
+        folder.notes.push(rel);
+    }
+
+    let title = note_stem(&dest.to_string_lossy()).to_string();
+    let mut content = format!("# {}\n\n", title);
+    render_index(&root, 0, &mut content);
+
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directories: {}", e))?;
+    }
+
+    write_atomic(&dest_path, content.as_bytes())
+        .map_err(|e| format!("Failed to write index: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn diamond_graph_exports_each_note_once() {
+        let vault = TempVault::new();
+        vault.write("A.md", "Start [[B]] [[C]]");
+        vault.write("B.md", "---\ntags: [x]\n---\nLeft [[D]]");
+        vault.write("C.md", "Right [[D]]");
+        vault.write("D.md", "Bottom");
+
+        let bundle = bundle_notes(vault.path(), "A.md", None).unwrap();
+
+        assert_eq!(bundle.matches("# D\n").count(), 1);
+        assert_eq!(
+            bundle,
+            "# A\n\nStart [B](#b) [C](#c)\n\n# B\n\nLeft [D](#d)\n\n# C\n\nRight [D](#d)\n\n# D\n\nBottom\n"
+        );
+    }
+
+    #[test]
+    fn rollup_is_sorted_by_path_with_headings() {
+        let vault = TempVault::new();
+        vault.write("journal/2024-06-12.md", "---\nmood: ok\n---\nSecond day\n");
+        vault.write("journal/2024-06-10.md", "First day");
+        vault.write("journal/2024-06-20.md", "Not matched");
+        vault.write("other/2024-06-11.md", "Elsewhere");
+
+        rollup_matching(vault.path(), "journal/2024-06-1?.md", "June.md", "\n---\n\n").unwrap();
+
+        assert_eq!(
+            vault.read("June.md"),
+            "# 2024-06-10\n\nFirst day\n\n---\n\n# 2024-06-12\n\nSecond day\n"
+        );
+        assert_eq!(vault.read("journal/2024-06-12.md"), "---\nmood: ok\n---\nSecond day\n");
+    }
+
+    #[test]
+    fn links_round_trip_through_both_formats() {
+        let vault = TempVault::new();
+        vault.write("Target.md", "");
+        vault.write("Source.md", "[[Target|the, \"target\"]] [[Missing]]");
+        let out = TempVault::new();
+
+        write_link_export(vault.path(), "JSON", &out.path().join("links.json")).unwrap();
+        let links: Vec<ExportedLink> = serde_json::from_str(&out.read("links.json")).unwrap();
+        let rows: Vec<(&str, &str, Option<&str>, bool, Option<&str>)> = links
+            .iter()
+            .map(|l| (l.source.as_str(), l.target.as_str(), l.display.as_deref(), l.resolved, l.resolved_path.as_deref()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("Source.md", "Target", Some("the, \"target\""), true, Some("Target.md")),
+                ("Source.md", "Missing", None, false, None),
+            ]
+        );
+
+        write_link_export(vault.path(), "csv", &out.path().join("links.csv")).unwrap();
+        assert_eq!(
+            out.read("links.csv"),
+            "source,target,display,resolved,resolved_path\n\
+             Source.md,Target,\"the, \"\"target\"\"\",true,Target.md\n\
+             Source.md,Missing,,false,\n"
+        );
+
+        assert!(write_link_export(vault.path(), "xml", &out.path().join("links.xml")).is_err());
+        assert!(write_link_export(vault.path(), "csv", out.path()).is_err());
+    }
+
+    #[test]
+    fn index_indents_by_folder_depth() {
+        let vault = TempVault::new();
+        vault.write("Home.md", "");
+        vault.write("Projects/Plan.md", "");
+        vault.write("Projects/Alpha/Spec.md", "");
+        vault.write("Drafts/Secret.md", "");
+        vault.write(".tauignore", "Drafts/\n");
+
+        write_vault_index(vault.path(), "Index.md").unwrap();
+        let expected = "# Index\n\n\
+            - Projects/\n  - Alpha/\n    - [[Projects/Alpha/Spec|Spec]]\n  - [[Projects/Plan|Plan]]\n\
+            - [[Home|Home]]\n";
+        assert_eq!(vault.read("Index.md"), expected);
+
+        // Re-running doesn't pick up the index itself
+        write_vault_index(vault.path(), "Index.md").unwrap();
+        assert_eq!(vault.read("Index.md"), expected);
+    }
+}
@@ -0,0 +1,650 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::frontmatter::{is_note_readonly, readonly_error};
+use crate::ids;
+use crate::fs::{check_note_title, collect_markdown_files, glob_matches, is_internal_name, log_bookkeeping, relative_path_string, sanitize_relative_path, unique_destination, with_note_content, write_atomic, AppState, BulkFailure};
+use crate::links::{is_id_link, note_link_path, note_stem, rewrite_links, NoteIndex};
+use crate::ordering;
+use crate::outline::forget_outlines;
+use crate::redirects;
+use crate::tags::{extract_tags, normalize_tag};
+use crate::trash::move_to_trash;
+
+// Rewrites links whose resolved note moved. Path-qualified links always get
+// the new path; bare links only change when the note's stem did. The notes
+// have already moved by now, so a note that can't be read or written is
+// logged and left for redirects to cover rather than failing the move.
+fn update_moved_links(base_path: &Path, index: &NoteIndex, moved: &HashMap<String, String>) {
+    for note in &index.notes {
+        let current = moved.get(note).unwrap_or(note);
+        let file_path = base_path.join(current);
+
+        if is_note_readonly(&file_path) {
+            continue;
+        }
+
+        // Not rewritten lossily: that would destroy the bytes that aren't UTF-8
+        let content = match fs::read_to_string(&file_path) {
+            Ok(content) => content,
+            Err(e) => {
+                log::warn!("Not updating moved links in {}: {}", current, e);
+                continue;
+            }
+        };
+
+        let (rewritten, changed) = rewrite_links(&content, |target| {
+            if is_id_link(target) {
+                return None;
+            }
+            let resolved = index.resolve_link_target(note, target)?;
+            let new_path = moved.get(&resolved)?;
+
+            if target.contains(['/', '\\']) {
+                Some(note_link_path(new_path))
+            } else if note_stem(new_path) != note_stem(&resolved) {
+                Some(note_stem(new_path).to_string())
+            } else {
+                None
+            }
+        });
+
+        if changed > 0 {
+            if let Err(e) = write_atomic(&file_path, rewritten.as_bytes()) {
+                log::warn!("Failed to update moved links in {}: {}", current, e);
+            }
+        }
+    }
+}
+
+// Moves everything in `rel` up into its parent and removes the folder.
+// Returns the new paths of the moved entries.
+fn flatten_into_parent(base_path: &Path, rel: &str) -> Result<Vec<String>, String> {
+    let folder_rel = sanitize_relative_path(rel)?;
+    let parent_rel = match folder_rel.parent() {
+        Some(parent) if !folder_rel.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => return Err("Cannot flatten the vault root".to_string()),
+    };
+
+    let folder_path = base_path.join(&folder_rel);
+    if !folder_path.is_dir() {
+        return Err(format!("Folder '{}' does not exist", rel));
+    }
+    let parent_path = base_path.join(&parent_rel);
+
+    let mut children: Vec<PathBuf> = fs::read_dir(&folder_path)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .flatten()
+        .filter(|entry| !is_internal_name(&entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect();
+    children.sort();
+
+    if let Some(readonly) = children.iter().find(|path| path.is_file() && is_note_readonly(path)) {
+        return Err(format!("Cannot move '{}': note is marked read-only", relative_path_string(base_path, readonly)?));
+    }
+
+    let index = NoteIndex::build(base_path)?;
+    let mut moved: HashMap<String, String> = HashMap::new();
+    let mut new_paths = Vec::new();
+
+    for child in children {
+        let file_name = child
+            .file_name()
+            .ok_or("Failed to get file name")?
+            .to_string_lossy()
+            .to_string();
+        let destination = unique_destination(&parent_path, &file_name);
+
+        // Notes nested in a moved subfolder move along with it
+        let notes: Vec<(PathBuf, PathBuf)> = if child.is_dir() {
+            collect_markdown_files(&child)?
+                .into_iter()
+                .filter_map(|note| {
+                    let inner = note.strip_prefix(&child).ok()?.to_path_buf();
+                    Some((note, destination.join(inner)))
+                })
+                .collect()
+        } else {
+            vec![(child.clone(), destination.clone())]
+        };
+
+        fs::rename(&child, &destination)
+            .map_err(|e| format!("Failed to move '{}': {}", file_name, e))?;
+
+        for (old_path, new_path) in notes {
+            moved.insert(relative_path_string(base_path, &old_path)?, relative_path_string(base_path, &new_path)?);
+        }
+        let (old_rel, new_rel) = (relative_path_string(base_path, &child)?, relative_path_string(base_path, &destination)?);
+        log_bookkeeping(&format!("Moved '{}' to '{}'", old_rel, new_rel), [
+            ("id map", ids::record_move(base_path, &old_rel, &new_rel)),
+            ("redirects", redirects::record_rename(base_path, &old_rel, &new_rel)),
+        ]);
+
+        new_paths.push(relative_path_string(base_path, &destination)?);
+    }
+
+    // Only app-managed files such as the order file are left behind
+    fs::remove_dir_all(&folder_path)
+        .map_err(|e| format!("Failed to remove folder: {}", e))?;
+
+    update_moved_links(base_path, &index, &moved);
+
+    Ok(new_paths)
+}
+
+#[tauri::command]
+pub async fn flatten_folder(app_handle: tauri::AppHandle, rel: String) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path.clone(),
+        None => return Err("No vault set".to_string()),
+    };
+
+    let moved = flatten_into_parent(&base_path, &rel)?;
+    forget_outlines(&mut state_guard.outlines, &rel);
+
+    Ok(moved)
+}
+
+// Clears everything inside a folder but keeps the folder itself and its
+// order file, pruned to match. Entries go to the trash unless `permanent`
+// is set. Returns the removed paths.
+fn clear_folder(base_path: &Path, rel: &str, permanent: bool) -> Result<Vec<String>, String> {
+    let folder_rel = sanitize_relative_path(&rel)?;
+    if folder_rel.as_os_str().is_empty() {
+        return Err("Cannot empty the vault root".to_string());
+    }
+
+    let folder_path = base_path.join(&folder_rel);
+    if !folder_path.is_dir() {
+        return Err(format!("Folder '{}' does not exist", rel));
+    }
+
+    // A symlinked folder could otherwise point the removal outside the vault
+    let canonical_base = base_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve vault path: {}", e))?;
+    let canonical_folder = folder_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve folder path: {}", e))?;
+    if !canonical_folder.starts_with(&canonical_base) {
+        return Err(format!("Path '{}' is outside the vault", rel));
+    }
+
+    let mut children: Vec<PathBuf> = fs::read_dir(&folder_path)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .flatten()
+        .filter(|entry| !is_internal_name(&entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect();
+    children.sort();
+
+    let mut removed = Vec::new();
+
+    for child in children {
+        let child_rel = relative_path_string(base_path, &child)?;
+
+        if permanent {
+            if child.is_dir() {
+                fs::remove_dir_all(&child)
+                    .map_err(|e| format!("Failed to delete directory: {}", e))?;
+            } else {
+                fs::remove_file(&child)
+                    .map_err(|e| format!("Failed to delete file: {}", e))?;
+            }
+        } else {
+            move_to_trash(base_path, Path::new(&child_rel))?;
+        }
+
+        log_bookkeeping(&format!("Deleted '{}'", child_rel), [
+            ("id map", ids::record_removal(base_path, &child_rel)),
+        ]);
+        removed.push(child_rel);
+    }
+
+    log_bookkeeping(&format!("Emptied '{}'", rel), [
+        ("order file", ordering::prune(&folder_path)),
+    ]);
+
+    Ok(removed)
+}
+
+#[tauri::command]
+pub async fn empty_folder(app_handle: tauri::AppHandle, rel: String, permanent: Option<bool>) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path.clone(),
+        None => return Err("No vault set".to_string()),
+    };
+
+    let removed = clear_folder(&base_path, &rel, permanent.unwrap_or(false))?;
+    forget_outlines(&mut state_guard.outlines, &rel);
+
+    Ok(removed)
+}
+
+// Moves every note tagged `tag` into `dest_dir`, numbering names that clash
+// and rewriting links that break. Notes already in `dest_dir` stay put.
+// Returns (old, new) path pairs.
+fn move_tagged_notes(base_path: &Path, tag: &str, dest_dir: &str) -> Result<Vec<(String, String)>, String> {
+    let tag = normalize_tag(tag).ok_or_else(|| format!("Invalid tag '{}'", tag))?;
+    let dest_rel = sanitize_relative_path(dest_dir)?;
+    let dest_path = base_path.join(&dest_rel);
+
+    if dest_path.is_file() {
+        return Err(format!("Destination '{}' is a file", dest_dir));
+    }
+
+    let index = NoteIndex::build(base_path)?;
+    let mut tagged = Vec::new();
+
+    for note in &index.notes {
+        let note_path = base_path.join(note);
+        if note_path.parent() == Some(dest_path.as_path()) {
+            continue;
+        }
+        if with_note_content(&note_path, |content| extract_tags(content).contains(&tag))? {
+            tagged.push(note_path);
+        }
+    }
+
+    if let Some(readonly) = tagged.iter().find(|path| is_note_readonly(path)) {
+        return Err(format!("Cannot move '{}': note is marked read-only", relative_path_string(base_path, readonly)?));
+    }
+
+    fs::create_dir_all(&dest_path)
+        .map_err(|e| format!("Failed to create destination folder: {}", e))?;
+
+    let mut moved: HashMap<String, String> = HashMap::new();
+    let mut new_names = Vec::new();
+    let mut pairs = Vec::new();
+
+    for note_path in tagged {
+        let file_name = note_path
+            .file_name()
+            .ok_or("Failed to get file name")?
+            .to_string_lossy()
+            .to_string();
+        let destination = unique_destination(&dest_path, &file_name);
+
+        fs::rename(&note_path, &destination)
+            .map_err(|e| format!("Failed to move '{}': {}", file_name, e))?;
+
+        let (old_rel, new_rel) = (relative_path_string(base_path, &note_path)?, relative_path_string(base_path, &destination)?);
+        let source_dir = note_path.parent().unwrap_or(base_path);
+        log_bookkeeping(&format!("Moved '{}' to '{}'", old_rel, new_rel), [
+            ("id map", ids::record_move(base_path, &old_rel, &new_rel)),
+            ("redirects", redirects::record_rename(base_path, &old_rel, &new_rel)),
+            ("source order file", ordering::prune(source_dir)),
+        ]);
+
+        if let Some(name) = destination.file_name() {
+            new_names.push(name.to_string_lossy().to_string());
+        }
+
+        moved.insert(old_rel.clone(), new_rel.clone());
+        pairs.push((old_rel, new_rel));
+    }
+
+    // Moved notes join the end of a custom order rather than falling back
+    // to alphabetical placement after it
+    let dest_order = ordering::load(&dest_path);
+    if !dest_order.is_empty() && !new_names.is_empty() {
+        let mut order = dest_order.names();
+        order.extend(new_names);
+        log_bookkeeping(&format!("Moved notes tagged '{}' into '{}'", tag, dest_dir), [
+            ("destination order file", ordering::save(&dest_path, order)),
+        ]);
+    }
+
+    update_moved_links(base_path, &index, &moved);
+
+    Ok(pairs)
+}
+
+// Returns the new paths of the notes moved by `move_tagged_notes`.
+#[tauri::command]
+pub async fn collect_tag_into_folder(app_handle: tauri::AppHandle, tag: String, dest_dir: String) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path.clone(),
+        None => return Err("No vault set".to_string()),
+    };
+
+    let mut new_paths = Vec::new();
+    for (old_rel, new_rel) in move_tagged_notes(&base_path, &tag, &dest_dir)? {
+        forget_outlines(&mut state_guard.outlines, &old_rel);
+        new_paths.push(new_rel);
+    }
+
+    Ok(new_paths)
+}
+
+// Outcome of `bulk_rename`: (old, new) pairs that were renamed, notes whose
+// new name was already taken, and notes that couldn't be renamed.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct BulkRenameResult {
+    pub renamed: Vec<(String, String)>,
+    pub skipped: Vec<String>,
+    pub failed: Vec<BulkFailure>,
+}
+
+// Renames every note matching `glob` by applying find/replace to its file
+// name without the `.md` extension. With `regex`, `find` is a regular
+// expression and `replace` may use `$1`-style groups. A rename that would
+// clash with an existing note or another rename in the batch is skipped.
+fn rename_matching(base_path: &Path, glob: &str, find: &str, replace: &str, regex: bool) -> Result<BulkRenameResult, String> {
+    if find.is_empty() {
+        return Err("Search text must not be empty".to_string());
+    }
+
+    let find_regex = if regex {
+        Some(Regex::new(find).map_err(|e| format!("Invalid regex: {}", e))?)
+    } else {
+        None
+    };
+
+    let index = NoteIndex::build(base_path)?;
+    let mut result = BulkRenameResult::default();
+    let mut moved: HashMap<String, String> = HashMap::new();
+    let mut claimed: HashSet<String> = HashSet::new();
+
+    for note in &index.notes {
+        if !glob_matches(glob, note) {
+            continue;
+        }
+
+        let stem = note_stem(note);
+        let new_stem = match &find_regex {
+            Some(find_regex) => find_regex.replace_all(stem, replace).to_string(),
+            None => stem.replace(find, replace),
+        };
+        if new_stem == stem {
+            continue;
+        }
+
+        let note_path = base_path.join(note);
+        let new_name = format!("{}.md", new_stem);
+
+        if let Err(e) = check_note_title(&new_stem) {
+            result.failed.push(BulkFailure { path: note.clone(), error: format!("Invalid new name '{}': {}", new_name, e) });
+            continue;
+        }
+        if is_note_readonly(&note_path) {
+            result.failed.push(BulkFailure { path: note.clone(), error: readonly_error(note) });
+            continue;
+        }
+
+        let new_path = note_path.with_file_name(&new_name);
+        let new_rel = relative_path_string(base_path, &new_path)?;
+
+        if new_path.exists() || !claimed.insert(new_rel.to_lowercase()) {
+            result.skipped.push(note.clone());
+            continue;
+        }
+
+        if let Err(e) = fs::rename(&note_path, &new_path) {
+            result.failed.push(BulkFailure { path: note.clone(), error: format!("Failed to rename entry: {}", e) });
+            continue;
+        }
+
+        let dir = note_path.parent().unwrap_or(base_path);
+        let old_name = note_path.file_name().unwrap_or_default().to_string_lossy();
+        log_bookkeeping(&format!("Renamed '{}' to '{}'", note, new_rel), [
+            ("id map", ids::record_move(base_path, note, &new_rel)),
+            ("redirects", redirects::record_rename(base_path, note, &new_rel)),
+            ("order file", ordering::rename(dir, &old_name, &new_name)),
+        ]);
+
+        moved.insert(note.clone(), new_rel.clone());
+        result.renamed.push((note.clone(), new_rel));
+    }
+
+    update_moved_links(base_path, &index, &moved);
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn bulk_rename(app_handle: tauri::AppHandle, glob: String, find: String, replace: String, regex: bool) -> Result<BulkRenameResult, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path.clone(),
+        None => return Err("No vault set".to_string()),
+    };
+
+    let result = rename_matching(&base_path, &glob, &find, &replace, regex)?;
+    for (old_rel, _) in &result.renamed {
+        forget_outlines(&mut state_guard.outlines, old_rel);
+    }
+
+    Ok(result)
+}
+
+// Creates `rel` and its home note `rel/<folder name>.md`, returning the
+// note's path. The note starts from the template note when one is given,
+// otherwise from a title heading. An existing folder just gains the note.
+#[tauri::command]
+pub async fn create_project(app_handle: tauri::AppHandle, rel: String, template: Option<String>) -> Result<String, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    create_project_folder(base_path, &rel, template.as_deref())
+}
+
+fn create_project_folder(base_path: &Path, rel: &str, template: Option<&str>) -> Result<String, String> {
+    let folder_rel = sanitize_relative_path(rel)?;
+    let name = match folder_rel.file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => return Err("Project folder must not be the vault root".to_string()),
+    };
+
+    let dir_path = base_path.join(&folder_rel);
+    if dir_path.exists() && !dir_path.is_dir() {
+        return Err(format!("'{}' is not a folder", rel));
+    }
+
+    let index_path = dir_path.join(format!("{}.md", name));
+    let index_rel = relative_path_string(base_path, &index_path)?;
+    if index_path.exists() {
+        return Err(format!("File '{}' already exists", index_rel));
+    }
+
+    let content = match template {
+        Some(template) => {
+            let template_path = base_path.join(sanitize_relative_path(template)?);
+            if !template_path.is_file() {
+                return Err(format!("Template '{}' does not exist", template));
+            }
+            fs::read_to_string(&template_path)
+                .map_err(|e| format!("Failed to read template: {}", e))?
+        }
+        None => format!("# {}\n", name),
+    };
+
+    fs::create_dir_all(&dir_path)
+        .map_err(|e| format!("Failed to create folder: {}", e))?;
+
+    write_atomic(&index_path, content.as_bytes())
+        .map_err(|e| format!("Failed to create note: {}", e))?;
+
+    Ok(index_rel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn flattened_notes_resolve_through_redirects() {
+        let vault = TempVault::new();
+        vault.write("Area/Sub/Note.md", "# Note");
+        vault.write("Area/Sub/Deeper/Inner.md", "# Inner");
+        // Read-only notes keep their stale links, which then rely on redirects
+        vault.write("Locked.md", "---\nreadonly: true\n---\n[[Area/Sub/Note]] [[Area/Sub/Deeper/Inner]]");
+
+        let mut moved = flatten_into_parent(vault.path(), "Area/Sub").unwrap();
+        moved.sort();
+        assert_eq!(moved, vec!["Area/Deeper", "Area/Note.md"]);
+        assert!(vault.read("Locked.md").contains("[[Area/Sub/Note]]"));
+
+        let index = NoteIndex::build(vault.path()).unwrap();
+        assert_eq!(index.resolve_link_target("Locked.md", "Area/Sub/Note").as_deref(), Some("Area/Note.md"));
+        assert_eq!(index.resolve_link_target("Locked.md", "Area/Sub/Deeper/Inner").as_deref(), Some("Area/Deeper/Inner.md"));
+    }
+
+    #[test]
+    fn flatten_numbers_names_taken_in_the_parent() {
+        let vault = TempVault::new();
+        vault.write("Area/Note.md", "parent's own");
+        vault.write("Area/Sub/Note.md", "from sub");
+        vault.write("Area/Sub/Other.md", "other");
+        vault.write("Index.md", "[[Area/Sub/Note]] [[Other]]");
+
+        let mut moved = flatten_into_parent(vault.path(), "Area/Sub").unwrap();
+        moved.sort();
+
+        assert_eq!(moved, vec!["Area/Note 1.md", "Area/Other.md"]);
+        assert_eq!(vault.read("Area/Note.md"), "parent's own");
+        assert_eq!(vault.read("Area/Note 1.md"), "from sub");
+        assert!(!vault.path().join("Area/Sub").exists());
+        // Path-qualified links follow the note; bare ones still resolve
+        assert_eq!(vault.read("Index.md"), "[[Area/Note 1]] [[Other]]");
+    }
+
+    #[test]
+    fn undecodable_note_does_not_fail_the_move() {
+        let vault = TempVault::new();
+        vault.write("Area/Sub/Note.md", "note");
+        vault.write("Latin1.md", b"caf\xe9 [[Area/Sub/Note]]");
+        vault.write("Index.md", "[[Area/Sub/Note]]");
+
+        assert_eq!(flatten_into_parent(vault.path(), "Area/Sub").unwrap(), vec!["Area/Note.md"]);
+        assert_eq!(vault.read("Index.md"), "[[Area/Note]]");
+        assert_eq!(fs::read(vault.path().join("Latin1.md")).unwrap(), b"caf\xe9 [[Area/Sub/Note]]");
+    }
+
+    #[test]
+    fn emptying_keeps_the_folder_and_removes_nested_content() {
+        let vault = TempVault::new();
+        vault.write("Area/Note.md", "note");
+        vault.write("Area/Sub/Deeper/Inner.md", "inner");
+        vault.write("Area/.tau_order.json", r#"{"order":["Sub","Note.md"]}"#);
+        vault.write("Other.md", "untouched");
+
+        let removed = clear_folder(vault.path(), "Area", true).unwrap();
+
+        assert_eq!(removed, vec!["Area/Note.md", "Area/Sub"]);
+        assert!(vault.path().join("Area").is_dir());
+        assert!(!vault.path().join("Area/Sub").exists());
+        assert!(!vault.path().join("Area/Note.md").exists());
+        assert!(ordering::load(&vault.path().join("Area")).is_empty());
+        assert_eq!(vault.read("Other.md"), "untouched");
+    }
+
+    #[test]
+    fn tagged_notes_from_two_folders_collect_with_collision() {
+        let vault = TempVault::new();
+        vault.write("Work/Plan.md", "#launch work plan");
+        vault.write("Home/Plan.md", "home plan #launch");
+        vault.write("Home/Other.md", "#unrelated");
+        vault.write("Launch/Plan.md", "#launch already here");
+        vault.write("Launch/.tau_order.json", r#"{"order":["Plan.md"]}"#);
+        vault.write("Work/.tau_order.json", r#"{"order":["Plan.md"]}"#);
+        vault.write("Index.md", "[[Work/Plan]] [[Home/Plan]]");
+
+        let pairs = move_tagged_notes(vault.path(), "launch", "Launch").unwrap();
+
+        assert_eq!(pairs, vec![
+            ("Home/Plan.md".to_string(), "Launch/Plan 1.md".to_string()),
+            ("Work/Plan.md".to_string(), "Launch/Plan 2.md".to_string()),
+        ]);
+        assert_eq!(vault.read("Launch/Plan.md"), "#launch already here");
+        assert_eq!(vault.read("Launch/Plan 2.md"), "#launch work plan");
+        assert!(vault.path().join("Home/Other.md").is_file());
+        assert_eq!(vault.read("Index.md"), "[[Launch/Plan 2]] [[Launch/Plan 1]]");
+
+        assert_eq!(ordering::load(&vault.path().join("Launch")).names(), vec!["Plan.md", "Plan 1.md", "Plan 2.md"]);
+        assert!(ordering::load(&vault.path().join("Work")).is_empty());
+    }
+
+    #[test]
+    fn regex_rename_skips_collisions() {
+        let vault = TempVault::new();
+        vault.write("Log/2024-01-05 Standup.md", "first");
+        vault.write("Log/2024-02-09 Standup.md", "second");
+        vault.write("Log/2024-03-01 Retro.md", "third");
+        vault.write("Index.md", "[[Log/2024-03-01 Retro]]");
+
+        let result = rename_matching(vault.path(), "Log/*.md", r"^\d{4}-\d{2}-\d{2} ", "", true).unwrap();
+
+        // Notes are visited in path order, so the first Standup wins the name
+        assert_eq!(result.renamed, vec![
+            ("Log/2024-01-05 Standup.md".to_string(), "Log/Standup.md".to_string()),
+            ("Log/2024-03-01 Retro.md".to_string(), "Log/Retro.md".to_string()),
+        ]);
+        assert_eq!(result.skipped, vec!["Log/2024-02-09 Standup.md"]);
+        assert!(result.failed.is_empty());
+
+        assert_eq!(vault.read("Log/Standup.md"), "first");
+        assert_eq!(vault.read("Log/2024-02-09 Standup.md"), "second");
+        assert_eq!(vault.read("Index.md"), "[[Log/Retro]]");
+    }
+
+    #[test]
+    fn rename_rejects_unusable_names() {
+        let vault = TempVault::new();
+        vault.write("a.md", "");
+        vault.write("b.md", "");
+        vault.write("c.md", "");
+
+        let attempts = [("a", "what?"), ("b", "..."), ("c", "tab\there")];
+        for (find, replace) in attempts {
+            let result = rename_matching(vault.path(), "*.md", find, replace, false).unwrap();
+            assert!(result.renamed.is_empty(), "renamed {} to {:?}", find, replace);
+            assert_eq!(result.failed.len(), 1);
+        }
+
+        assert!(vault.path().join("a.md").is_file());
+        assert!(vault.path().join("b.md").is_file());
+    }
+
+    #[test]
+    fn projects_get_a_home_note_in_new_and_existing_folders() {
+        let vault = TempVault::new();
+        vault.write("Templates/Project.md", "# Project\n\n## Goals\n");
+
+        assert_eq!(create_project_folder(vault.path(), "Work/Launch", None).unwrap(), "Work/Launch/Launch.md");
+        assert_eq!(vault.read("Work/Launch/Launch.md"), "# Launch\n");
+
+        vault.write("Garden/notes.md", "existing");
+        assert_eq!(
+            create_project_folder(vault.path(), "Garden", Some("Templates/Project.md")).unwrap(),
+            "Garden/Garden.md"
+        );
+        assert_eq!(vault.read("Garden/Garden.md"), "# Project\n\n## Goals\n");
+        assert_eq!(vault.read("Garden/notes.md"), "existing");
+
+        // Running it again would overwrite the home note
+        assert!(create_project_folder(vault.path(), "Garden", None).is_err());
+    }
+}
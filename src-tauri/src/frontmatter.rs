@@ -0,0 +1,535 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use tauri::Manager;
+
+use crate::fs::{collect_markdown_files, relative_path_string, with_note_content, write_atomic, AppState, BulkFailure, BulkResult};
+
+pub type Frontmatter = serde_yaml::Mapping;
+
+// Splits a note into its YAML frontmatter block and the body that follows.
+// Returns None when the note doesn't open with a `---` line or the block is
+// never closed.
+pub(crate) fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+        if trimmed == "---" || trimmed == "..." {
+            let yaml = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            return Some((yaml, body));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+pub(crate) fn parse_frontmatter(content: &str) -> Option<Frontmatter> {
+    let (yaml, _) = split_frontmatter(content)?;
+
+    if yaml.trim().is_empty() {
+        return Some(Frontmatter::new());
+    }
+
+    serde_yaml::from_str(yaml).ok()
+}
+
+// Rebuilds a note with `frontmatter` in place of its current block, or
+// prepended when it has none. An empty mapping drops the block entirely.
+pub(crate) fn with_frontmatter(content: &str, frontmatter: &Frontmatter) -> Result<String, String> {
+    let body = split_frontmatter(content).map(|(_, body)| body).unwrap_or(content);
+
+    if frontmatter.is_empty() {
+        return Ok(body.to_string());
+    }
+
+    let yaml = serde_yaml::to_string(frontmatter)
+        .map_err(|e| format!("Failed to serialize frontmatter: {}", e))?;
+
+    Ok(format!("---\n{}---\n{}", yaml, body))
+}
+
+// Alternative names a note can be linked by, from `aliases` (or `alias`),
+// given either as a YAML list or a single string.
+pub(crate) fn note_aliases(content: &str) -> Vec<String> {
+    let frontmatter = match parse_frontmatter(content) {
+        Some(frontmatter) => frontmatter,
+        None => return Vec::new(),
+    };
+
+    let value = frontmatter.get("aliases").or_else(|| frontmatter.get("alias"));
+
+    match value {
+        Some(serde_yaml::Value::Sequence(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str())
+            .map(|alias| alias.trim().to_string())
+            .filter(|alias| !alias.is_empty())
+            .collect(),
+        Some(serde_yaml::Value::String(alias)) if !alias.trim().is_empty() => {
+            vec![alias.trim().to_string()]
+        }
+        _ => Vec::new(),
+    }
+}
+
+// Checks for `readonly: true` without parsing the whole frontmatter: only
+// the opening block is read, line by line, and only top-level keys count.
+pub(crate) fn is_note_readonly(path: &Path) -> bool {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return false,
+    };
+
+    let mut lines = BufReader::new(file).lines();
+    match lines.next() {
+        Some(Ok(first)) if first.trim_end() == "---" => {}
+        _ => return false,
+    }
+
+    for line in lines {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => return false,
+        };
+        let trimmed = line.trim_end();
+
+        if trimmed == "---" || trimmed == "..." {
+            return false;
+        }
+
+        if let Some(value) = trimmed.strip_prefix("readonly:") {
+            let value = value.trim().trim_matches(['"', '\'']);
+            return matches!(value.to_lowercase().as_str(), "true" | "yes");
+        }
+    }
+
+    false
+}
+
+// Error returned when a command would modify a read-only note. The prefix
+// lets the frontend tell this apart from ordinary failures.
+pub(crate) fn readonly_error(rel: &str) -> String {
+    format!("READONLY: '{}' is marked read-only", rel)
+}
+
+// Guard for commands that take a `force` flag: read-only notes are refused
+// unless the caller explicitly overrides.
+pub(crate) fn check_writable(path: &Path, rel: &str, force: bool) -> Result<(), String> {
+    if !force && is_note_readonly(path) {
+        return Err(readonly_error(rel));
+    }
+    Ok(())
+}
+
+// Frontmatter of every note in `rels`, or of the whole vault when `rels` is
+// empty. Notes without frontmatter, with invalid YAML or that can't be read
+// come back as None instead of failing the batch.
+pub(crate) fn frontmatter_batch(base_path: &Path, rels: Vec<String>) -> Result<Vec<(String, Option<Frontmatter>)>, String> {
+    let rels = if rels.is_empty() {
+        collect_markdown_files(base_path)?
+            .iter()
+            .map(|path| relative_path_string(base_path, path))
+            .collect::<Result<Vec<_>, String>>()?
+    } else {
+        rels
+    };
+
+    Ok(rels
+        .into_iter()
+        .map(|rel| {
+            let frontmatter = with_note_content(&base_path.join(&rel), parse_frontmatter)
+                .ok()
+                .flatten();
+            (rel, frontmatter)
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn read_frontmatter_batch(app_handle: tauri::AppHandle, rels: Vec<String>) -> Result<Vec<(String, Option<Frontmatter>)>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    frontmatter_batch(base_path, rels)
+}
+
+// Looks up `field` with dot notation descending into nested mappings, so
+// `project.status` reads `status` under `project`.
+pub(crate) fn lookup_field<'a>(frontmatter: &'a Frontmatter, field: &str) -> Option<&'a serde_yaml::Value> {
+    let mut parts = field.split('.');
+    let mut value = frontmatter.get(parts.next()?)?;
+
+    for part in parts {
+        value = value.as_mapping()?.get(part)?;
+    }
+
+    Some(value)
+}
+
+// Scalars compared as strings, so `true`, `3` and `"3"` all match their text.
+fn scalar_text(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(text) => Some(text.clone()),
+        serde_yaml::Value::Bool(flag) => Some(flag.to_string()),
+        serde_yaml::Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn field_matches(value: &serde_yaml::Value, wanted: &str) -> bool {
+    match value {
+        serde_yaml::Value::Sequence(items) => items.iter().any(|item| scalar_text(item).as_deref() == Some(wanted)),
+        _ => scalar_text(value).as_deref() == Some(wanted),
+    }
+}
+
+#[tauri::command]
+pub async fn query_by_frontmatter(app_handle: tauri::AppHandle, field: String, value: String) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    notes_matching(base_path, &field, &value)
+}
+
+fn notes_matching(base_path: &Path, field: &str, value: &str) -> Result<Vec<String>, String> {
+    Ok(frontmatter_batch(base_path, Vec::new())?
+        .into_iter()
+        .filter(|(_, frontmatter)| {
+            frontmatter
+                .as_ref()
+                .and_then(|frontmatter| lookup_field(frontmatter, field))
+                .is_some_and(|found| field_matches(found, value))
+        })
+        .map(|(rel, _)| rel)
+        .collect())
+}
+
+// Renames top-level key `old` to `new` in every note that has it, keeping its
+// position and value. Notes that already have `new` are reported as skipped
+// and left untouched.
+fn rename_key_in_notes(base_path: &Path, old: &str, new: &str) -> Result<BulkResult, String> {
+    if old.trim().is_empty() || new.trim().is_empty() {
+        return Err("Frontmatter keys must not be empty".to_string());
+    }
+
+    let mut result = BulkResult::default();
+
+    for path in collect_markdown_files(base_path)? {
+        let rel = relative_path_string(base_path, &path)?;
+        // Rewriting a lossily decoded note would corrupt its bytes, so an
+        // unreadable one is reported instead
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                result.failed.push(BulkFailure { path: rel, error: format!("Failed to read note: {}", e) });
+                continue;
+            }
+        };
+
+        let frontmatter = match parse_frontmatter(&content) {
+            Some(frontmatter) if frontmatter.contains_key(old) => frontmatter,
+            _ => continue,
+        };
+
+        if frontmatter.contains_key(new) {
+            result.skipped.push(rel);
+            continue;
+        }
+
+        if is_note_readonly(&path) {
+            result.failed.push(BulkFailure { error: readonly_error(&rel), path: rel });
+            continue;
+        }
+
+        let renamed: Frontmatter = frontmatter
+            .into_iter()
+            .map(|(key, value)| match key.as_str() {
+                Some(name) if name == old => (serde_yaml::Value::String(new.to_string()), value),
+                _ => (key, value),
+            })
+            .collect();
+
+        let written = with_frontmatter(&content, &renamed).and_then(|updated| {
+            write_atomic(&path, updated.as_bytes()).map_err(|e| format!("Failed to write note: {}", e))
+        });
+
+        match written {
+            Ok(()) => result.succeeded.push(rel),
+            Err(error) => result.failed.push(BulkFailure { path: rel, error }),
+        }
+    }
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn rename_frontmatter_key(app_handle: tauri::AppHandle, old: String, new: String) -> Result<BulkResult, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    rename_key_in_notes(base_path, &old, &new)
+}
+
+// Orders `replacement` so keys the note already had keep their positions and
+// new keys follow in the order given. Rewriting the same properties then
+// leaves the block byte-for-byte unchanged.
+fn keep_key_order(existing: Option<&Frontmatter>, mut replacement: Frontmatter) -> Frontmatter {
+    let mut ordered = Frontmatter::new();
+
+    if let Some(existing) = existing {
+        for key in existing.keys() {
+            if let Some(value) = replacement.remove(key) {
+                ordered.insert(key.clone(), value);
+            }
+        }
+    }
+
+    ordered.extend(replacement);
+    ordered
+}
+
+// Replaces the note's frontmatter block wholesale, inserting one when the
+// note has none. The body is left untouched; an empty mapping removes the
+// block.
+#[tauri::command]
+pub async fn set_frontmatter(app_handle: tauri::AppHandle, rel: String, frontmatter: Frontmatter) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    replace_frontmatter(base_path, &rel, frontmatter)
+}
+
+fn replace_frontmatter(base_path: &Path, rel: &str, frontmatter: Frontmatter) -> Result<(), String> {
+    let file_path = base_path.join(rel);
+
+    if !file_path.is_file() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    if is_note_readonly(&file_path) {
+        return Err(readonly_error(rel));
+    }
+
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read note: {}", e))?;
+
+    let frontmatter = keep_key_order(parse_frontmatter(&content).as_ref(), frontmatter);
+    let updated = with_frontmatter(&content, &frontmatter)?;
+
+    write_atomic(&file_path, updated.as_bytes())
+        .map_err(|e| format!("Failed to write note: {}", e))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeyCount {
+    pub key: String,
+    // Notes using the key, not occurrences
+    pub count: usize,
+}
+
+// Keys of `frontmatter`, with dot-paths for nested mappings when `nested` is
+// set (`project` and `project.status`).
+fn collect_keys(frontmatter: &Frontmatter, prefix: &str, nested: bool, keys: &mut BTreeSet<String>) {
+    for (key, value) in frontmatter {
+        let name = match key.as_str() {
+            Some(name) => format!("{}{}", prefix, name),
+            None => continue,
+        };
+
+        if nested {
+            if let serde_yaml::Value::Mapping(child) = value {
+                collect_keys(child, &format!("{}.", name), nested, keys);
+            }
+        }
+        keys.insert(name);
+    }
+}
+
+// Every frontmatter key in the vault with the number of notes using it,
+// most used first.
+#[tauri::command]
+pub async fn frontmatter_keys(app_handle: tauri::AppHandle, nested: Option<bool>) -> Result<Vec<KeyCount>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    count_keys(base_path, nested.unwrap_or(false))
+}
+
+fn count_keys(base_path: &Path, nested: bool) -> Result<Vec<KeyCount>, String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for (_, frontmatter) in frontmatter_batch(base_path, Vec::new())? {
+        let mut keys = BTreeSet::new();
+        if let Some(frontmatter) = frontmatter {
+            collect_keys(&frontmatter, "", nested, &mut keys);
+        }
+        for key in keys {
+            *counts.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    let mut key_counts: Vec<KeyCount> = counts
+        .into_iter()
+        .map(|(key, count)| KeyCount { key, count })
+        .collect();
+    key_counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+
+    Ok(key_counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn readonly_notes_need_force() {
+        let vault = TempVault::new();
+        let locked = vault.write("Locked.md", "---\ntitle: Ref\nreadonly: true\n---\nbody");
+        let open = vault.write("Open.md", "---\nreadonly: false\n---\nbody");
+        let nested = vault.write("Nested.md", "---\nmeta:\n  readonly: true\n---\n");
+
+        assert!(check_writable(&locked, "Locked.md", false).unwrap_err().starts_with("READONLY"));
+        assert!(check_writable(&locked, "Locked.md", true).is_ok());
+        assert!(check_writable(&open, "Open.md", false).is_ok());
+        assert!(check_writable(&nested, "Nested.md", false).is_ok());
+        assert!(check_writable(&vault.path().join("New.md"), "New.md", false).is_ok());
+    }
+
+    #[test]
+    fn key_rename_skips_notes_that_already_have_the_new_key() {
+        let vault = TempVault::new();
+        vault.write("Old.md", "---\ntitle: Old\nauthor: Ann\ndate: 2024\n---\nbody");
+        vault.write("Both.md", "---\nauthor: Ann\nwriter: Bob\n---\nbody");
+        vault.write("Latin1.md", b"---\nauthor: caf\xe9\n---\n");
+        vault.write("Plain.md", "no frontmatter");
+
+        let result = rename_key_in_notes(vault.path(), "author", "writer").unwrap();
+
+        assert_eq!(result.succeeded, vec!["Old.md"]);
+        assert_eq!(result.skipped, vec!["Both.md"]);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].path, "Latin1.md");
+
+        assert_eq!(vault.read("Old.md"), "---\ntitle: Old\nwriter: Ann\ndate: 2024\n---\nbody");
+        assert_eq!(vault.read("Both.md"), "---\nauthor: Ann\nwriter: Bob\n---\nbody");
+    }
+
+    #[test]
+    fn invalid_frontmatter_is_none_without_failing_the_batch() {
+        let vault = TempVault::new();
+        vault.write("Valid.md", "---\nstatus: active\n---\nbody");
+        vault.write("Invalid.md", "---\nstatus: [unclosed\n---\nbody");
+        vault.write("Plain.md", "no frontmatter");
+
+        let rels = vec!["Valid.md".to_string(), "Invalid.md".to_string(), "Missing.md".to_string()];
+        let batch = frontmatter_batch(vault.path(), rels).unwrap();
+
+        let found: Vec<(&str, Option<&str>)> = batch
+            .iter()
+            .map(|(rel, frontmatter)| (rel.as_str(), frontmatter.as_ref().map(|f| f["status"].as_str().unwrap())))
+            .collect();
+        assert_eq!(found, vec![("Valid.md", Some("active")), ("Invalid.md", None), ("Missing.md", None)]);
+
+        // An empty list walks the whole vault
+        let mut walked: Vec<String> = frontmatter_batch(vault.path(), Vec::new())
+            .unwrap()
+            .into_iter()
+            .map(|(rel, _)| rel)
+            .collect();
+        walked.sort();
+        assert_eq!(walked, vec!["Invalid.md", "Plain.md", "Valid.md"]);
+    }
+
+    #[test]
+    fn queries_match_scalars_and_list_members() {
+        let vault = TempVault::new();
+        vault.write("Scalar.md", "---\nstatus: active\npriority: 3\n---\n");
+        vault.write("List.md", "---\nstatus: [draft, active]\n---\n");
+        vault.write("Nested.md", "---\nproject:\n  status: active\n---\n");
+        vault.write("Other.md", "---\nstatus: done\n---\n");
+        vault.write("Missing.md", "---\ntitle: No status\n---\n");
+
+        let query = |field: &str, value: &str| {
+            let mut found = notes_matching(vault.path(), field, value).unwrap();
+            found.sort();
+            found
+        };
+
+        assert_eq!(query("status", "active"), vec!["List.md", "Scalar.md"]);
+        assert_eq!(query("priority", "3"), vec!["Scalar.md"]);
+        assert_eq!(query("project.status", "active"), vec!["Nested.md"]);
+        assert!(query("owner", "active").is_empty());
+    }
+
+    #[test]
+    fn setting_frontmatter_replaces_or_adds_the_block() {
+        let vault = TempVault::new();
+        vault.write("Existing.md", "---\ntitle: Old\nstatus: draft\nextra: gone\n---\n# Body\n");
+        vault.write("Bare.md", "# Body\n");
+
+        let frontmatter: Frontmatter = serde_yaml::from_str("owner: Ann\nstatus: done\ntitle: New").unwrap();
+
+        // Existing keys keep their place, dropped keys go, new keys follow
+        replace_frontmatter(vault.path(), "Existing.md", frontmatter.clone()).unwrap();
+        assert_eq!(vault.read("Existing.md"), "---\ntitle: New\nstatus: done\nowner: Ann\n---\n# Body\n");
+
+        replace_frontmatter(vault.path(), "Bare.md", frontmatter).unwrap();
+        assert_eq!(vault.read("Bare.md"), "---\nowner: Ann\nstatus: done\ntitle: New\n---\n# Body\n");
+    }
+
+    #[test]
+    fn key_counts_are_per_note() {
+        let vault = TempVault::new();
+        vault.write("One.md", "---\nstatus: active\nproject:\n  phase: 1\n---\n");
+        vault.write("Two.md", "---\nstatus: done\n---\n");
+        vault.write("Plain.md", "no frontmatter");
+
+        let counts = |nested| -> Vec<(String, usize)> {
+            count_keys(vault.path(), nested)
+                .unwrap()
+                .into_iter()
+                .map(|key_count| (key_count.key, key_count.count))
+                .collect()
+        };
+
+        assert_eq!(counts(false), vec![("status".to_string(), 2), ("project".to_string(), 1)]);
+        assert_eq!(
+            counts(true),
+            vec![("status".to_string(), 2), ("project".to_string(), 1), ("project.phase".to_string(), 1)]
+        );
+    }
+}
@@ -1,13 +1,29 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
-use std::collections::HashMap;
-use tauri::Manager;
+use std::path::{Component, Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+use tauri::{Emitter, Manager};
 use regex::Regex;
 
+use crate::config::{read_vault_config, LineEnding};
+use crate::frontmatter::check_writable;
+use crate::ids;
+use crate::ordering;
+use crate::outline::{cache_outline, forget_outlines, CachedOutline, OUTLINE_UPDATED_EVENT};
+use crate::redirects;
+use crate::timing::timed;
+use crate::trash::{move_to_trash, TRASH_DIR};
+use crate::ui_state;
+
 #[derive(Default)]
 pub struct AppState {
     pub vault_path: Option<PathBuf>,
+    // Vault that set_vault last refused because another instance holds its lock
+    pub blocked_vault_path: Option<PathBuf>,
+    // The vault root was found missing by the last health check
+    pub vault_missing: bool,
+    // Outlines of notes saved through write_note, keyed by relative path
+    pub outlines: HashMap<String, CachedOutline>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,6 +32,16 @@ pub struct Entry {
     pub path: String,
     pub is_dir: bool,
     pub modified: String,
+    pub created: Option<String>,
+    // File size in bytes; 0 for directories
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntryPage {
+    pub entries: Vec<Entry>,
+    pub total: usize,
+    pub offset: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -27,6 +53,20 @@ pub struct Link {
     pub length: usize,
 }
 
+// Per-path outcome of a command applied to several entries at once.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct BulkResult {
+    pub succeeded: Vec<String>,
+    pub skipped: Vec<String>,
+    pub failed: Vec<BulkFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkFailure {
+    pub path: String,
+    pub error: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LinkSuggestion {
     pub note_name: String,
@@ -35,8 +75,20 @@ pub struct LinkSuggestion {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct FileOrder {
-    pub order: Vec<String>,
+pub struct ExtCount {
+    pub ext: String,
+    pub count: usize,
+}
+
+// Bytes on disk by category. `total_bytes` covers everything, including
+// the trash; the other fields partition it.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DiskUsage {
+    pub total_bytes: u64,
+    pub markdown_bytes: u64,
+    pub attachment_bytes: u64,
+    pub internal_bytes: u64,
+    pub trash_bytes: u64,
 }
 
 #[tauri::command]
@@ -49,7 +101,22 @@ pub async fn set_vault(app_handle: tauri::AppHandle, path: String) -> Result<(),
         return Err(format!("Path '{}' does not exist", path));
     }
     
+    if let Err(e) = crate::lock::acquire_vault_lock(&vault_path) {
+        state_guard.blocked_vault_path = Some(vault_path);
+        return Err(e);
+    }
+    
+    // Hand back the lock on the vault we're switching away from
+    if let Some(previous) = &state_guard.vault_path {
+        if previous != &vault_path {
+            crate::lock::release_vault_lock(previous);
+        }
+    }
+    
     state_guard.vault_path = Some(vault_path);
+    state_guard.blocked_vault_path = None;
+    state_guard.vault_missing = false;
+    state_guard.outlines.clear();
     Ok(())
 }
 
@@ -61,132 +128,224 @@ pub async fn get_vault(app_handle: tauri::AppHandle) -> Result<Option<String>, S
     Ok(state_guard.vault_path.as_ref().map(|p| p.to_string_lossy().to_string()))
 }
 
-fn get_order_file_path(dir_path: &PathBuf) -> PathBuf {
-    dir_path.join(".tau_order.json")
-}
-
-fn read_file_order(dir_path: &PathBuf) -> HashMap<String, usize> {
-    let order_file = get_order_file_path(dir_path);
-    let mut order_map = HashMap::new();
+// Normalizes a user- or archive-supplied relative path, rejecting anything
+// that could land outside the directory it is joined onto.
+pub(crate) fn sanitize_relative_path(rel: &str) -> Result<PathBuf, String> {
+    let normalized = rel.replace('\\', "/");
+    let mut clean = PathBuf::new();
 
-    if let Ok(content) = fs::read_to_string(&order_file) {
-        if let Ok(file_order) = serde_json::from_str::<FileOrder>(&content) {
-            for (index, file_name) in file_order.order.iter().enumerate() {
-                order_map.insert(file_name.clone(), index);
+    for component in Path::new(&normalized).components() {
+        match component {
+            Component::Normal(part) => clean.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                return Err(format!("Path '{}' must not contain '..'", rel));
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(format!("Path '{}' must be relative", rel));
             }
         }
     }
 
-    order_map
+    Ok(clean)
 }
 
-fn write_file_order(dir_path: &PathBuf, order: Vec<String>) -> Result<(), String> {
-    let order_file = get_order_file_path(dir_path);
-    let file_order = FileOrder { order };
-    let content = serde_json::to_string_pretty(&file_order)
-        .map_err(|e| format!("Failed to serialize order: {}", e))?;
+// Resolves `..` and `.` without touching the filesystem. Returns None when
+// the path climbs above the vault root.
+pub(crate) fn normalize_lexically(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => normalized.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+    }
 
-    fs::write(&order_file, content)
-        .map_err(|e| format!("Failed to write order file: {}", e))?;
+    Some(normalized)
+}
 
-    Ok(())
+fn system_time_secs(time: std::time::SystemTime) -> Result<u64, String> {
+    Ok(time
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| format!("Failed to convert time: {}", e))?
+        .as_secs())
 }
 
-#[tauri::command]
-pub async fn list_entries(app_handle: tauri::AppHandle, path: Option<String>) -> Result<Vec<Entry>, String> {
-    let state = app_handle.state::<std::sync::Mutex<AppState>>();
-    let state_guard = state.lock().map_err(|e| e.to_string())?;
+pub(crate) fn build_entry(base_path: &Path, path: &Path, metadata: &fs::Metadata) -> Result<Entry, String> {
+    let relative_path = path
+        .strip_prefix(base_path)
+        .map_err(|e| format!("Failed to create relative path: {}", e))?
+        .to_string_lossy()
+        .to_string();
     
-    let base_path = match &state_guard.vault_path {
-        Some(vault_path) => vault_path.clone(),
-        None => return Err("No vault set".to_string()),
-    };
+    let name = path
+        .file_name()
+        .ok_or("Failed to get file name")?
+        .to_string_lossy()
+        .to_string();
     
-    let target_path = if let Some(rel_path) = path {
-        base_path.join(rel_path)
-    } else {
-        base_path.clone()
-    };
+    let modified = metadata
+        .modified()
+        .map_err(|e| format!("Failed to get modified time: {}", e))?;
     
-    if !target_path.exists() {
-        return Err(format!("Path '{}' does not exist", target_path.display()));
-    }
+    // Not every platform/filesystem records a birth time
+    let created = match metadata.created() {
+        Ok(created) => Some(system_time_secs(created)?.to_string()),
+        Err(_) => None,
+    };
     
+    Ok(Entry {
+        name,
+        path: relative_path,
+        is_dir: metadata.is_dir(),
+        modified: system_time_secs(modified)?.to_string(),
+        created,
+        size: if metadata.is_dir() { 0 } else { metadata.len() },
+    })
+}
+
+// Reads a directory's entries in display order: directories first, then
+// files, each group following the custom order file when present. App-managed
+// files and the trash are left out unless `include_hidden` is set.
+fn read_sorted_entries(base_path: &Path, target_path: &Path, include_hidden: bool) -> Result<Vec<Entry>, String> {
     let mut entries = Vec::new();
     
-    let dir_entries = fs::read_dir(&target_path)
+    let dir_entries = fs::read_dir(target_path)
         .map_err(|e| format!("Failed to read directory: {}", e))?;
     
     for entry in dir_entries {
         let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let path = entry.path();
-        let metadata = entry.metadata().map_err(|e| format!("Failed to read metadata: {}", e))?;
-        
-        let relative_path = path
-            .strip_prefix(&base_path)
-            .map_err(|e| format!("Failed to create relative path: {}", e))?
-            .to_string_lossy()
-            .to_string();
+        let name = entry.file_name().to_string_lossy().to_string();
         
-        let name = path
-            .file_name()
-            .ok_or("Failed to get file name")?
-            .to_string_lossy()
-            .to_string();
+        if !include_hidden && (is_internal_name(&name) || name == TRASH_DIR) {
+            continue;
+        }
         
-        let modified = metadata
-            .modified()
-            .map_err(|e| format!("Failed to get modified time: {}", e))?
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| format!("Failed to convert time: {}", e))?
-            .as_secs()
-            .to_string();
+        let path = entry.path();
+        let metadata = entry.metadata().map_err(|e| format!("Failed to read metadata: {}", e))?;
         
-        entries.push(Entry {
-            name,
-            path: relative_path,
-            is_dir: metadata.is_dir(),
-            modified,
-        });
+        entries.push(build_entry(base_path, &path, &metadata)?);
     }
     
-    // Read custom order if it exists
-    let order_map = read_file_order(&target_path);
-
     // Sort entries: directories first, then files, using custom order if available
-    entries.sort_by(|a, b| {
-        match (a.is_dir, b.is_dir) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => {
-                // Both are same type (dir or file), check custom order
-                let a_order = order_map.get(&a.name);
-                let b_order = order_map.get(&b.name);
-
-                match (a_order, b_order) {
-                    (Some(a_idx), Some(b_idx)) => a_idx.cmp(b_idx),
-                    (Some(_), None) => std::cmp::Ordering::Less,
-                    (None, Some(_)) => std::cmp::Ordering::Greater,
-                    (None, None) => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
-                }
-            }
-        }
-    });
+    ordering::apply_sort(&mut entries, &ordering::load(target_path));
 
     Ok(entries)
 }
 
-#[tauri::command]
-pub async fn create_note(app_handle: tauri::AppHandle, rel: String) -> Result<(), String> {
+fn resolve_list_path(app_handle: &tauri::AppHandle, path: Option<String>) -> Result<(PathBuf, PathBuf), String> {
     let state = app_handle.state::<std::sync::Mutex<AppState>>();
     let state_guard = state.lock().map_err(|e| e.to_string())?;
     
     let base_path = match &state_guard.vault_path {
-        Some(vault_path) => vault_path,
+        Some(vault_path) => vault_path.clone(),
         None => return Err("No vault set".to_string()),
     };
     
-    let file_path = base_path.join(&rel);
+    let target_path = if let Some(rel_path) = path {
+        base_path.join(rel_path)
+    } else {
+        base_path.clone()
+    };
+    
+    if !target_path.exists() {
+        return Err(format!("Path '{}' does not exist", target_path.display()));
+    }
+    
+    Ok((base_path, target_path))
+}
+
+#[tauri::command]
+pub async fn list_entries(app_handle: tauri::AppHandle, path: Option<String>, include_hidden: Option<bool>) -> Result<Vec<Entry>, String> {
+    let (base_path, target_path) = resolve_list_path(&app_handle, path)?;
+    read_sorted_entries(&base_path, &target_path, include_hidden.unwrap_or(false))
+}
+
+// Entry names in exactly the order list_entries returns them, so the
+// frontend can place entries optimistically without its own sort.
+#[tauri::command]
+pub async fn effective_order(app_handle: tauri::AppHandle, dir_path: Option<String>, include_hidden: Option<bool>) -> Result<Vec<String>, String> {
+    let (base_path, target_path) = resolve_list_path(&app_handle, dir_path)?;
+    listed_names(&base_path, &target_path, include_hidden.unwrap_or(false))
+}
+
+fn listed_names(base_path: &Path, target_path: &Path, include_hidden: bool) -> Result<Vec<String>, String> {
+    Ok(read_sorted_entries(base_path, target_path, include_hidden)?
+        .into_iter()
+        .map(|entry| entry.name)
+        .collect())
+}
+
+// Where an entry sits among its siblings of the same kind, as listed:
+// folders and files are counted separately. `index` is zero-based, like
+// the index taken by move_to_position.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Position {
+    pub index: usize,
+    pub total: usize,
+}
+
+#[tauri::command]
+pub async fn position_in_folder(app_handle: tauri::AppHandle, rel: String) -> Result<Position, String> {
+    let (base_path, entry_path) = resolve_list_path(&app_handle, Some(rel.clone()))?;
+    folder_position(&base_path, &entry_path, &rel)
+}
+
+fn folder_position(base_path: &Path, entry_path: &Path, rel: &str) -> Result<Position, String> {
+    let parent = entry_path
+        .parent()
+        .ok_or_else(|| format!("Path '{}' has no parent folder", rel))?;
+    let name = entry_path
+        .file_name()
+        .ok_or("Failed to get file name")?
+        .to_string_lossy()
+        .to_string();
+    let is_dir = entry_path.is_dir();
+
+    let siblings: Vec<Entry> = read_sorted_entries(base_path, parent, false)?
+        .into_iter()
+        .filter(|entry| entry.is_dir == is_dir)
+        .collect();
+
+    let index = siblings
+        .iter()
+        .position(|entry| entry.name == name)
+        .ok_or_else(|| format!("Could not find '{}' in its folder", rel))?;
+
+    Ok(Position { index, total: siblings.len() })
+}
+
+// Paginated listing for virtualized views. Sorting happens before slicing so
+// consecutive pages never overlap or skip entries.
+#[tauri::command]
+pub async fn list_entries_page(app_handle: tauri::AppHandle, path: Option<String>, offset: Option<usize>, limit: Option<usize>, include_hidden: Option<bool>) -> Result<EntryPage, String> {
+    let (base_path, target_path) = resolve_list_path(&app_handle, path)?;
+    let entries = read_sorted_entries(&base_path, &target_path, include_hidden.unwrap_or(false))?;
+    
+    Ok(paginate(entries, offset, limit))
+}
+
+fn paginate(entries: Vec<Entry>, offset: Option<usize>, limit: Option<usize>) -> EntryPage {
+    let total = entries.len();
+    let offset = offset.unwrap_or(0);
+    let entries = entries
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    
+    EntryPage { entries, total, offset }
+}
+
+fn create_note_file(base_path: &Path, rel: &str, content: Option<&str>) -> Result<(), String> {
+    let file_path = base_path.join(rel);
     
     // Create parent directories if they don't exist
     if let Some(parent) = file_path.parent() {
@@ -194,6 +353,15 @@ pub async fn create_note(app_handle: tauri::AppHandle, rel: String) -> Result<()
             .map_err(|e| format!("Failed to create parent directories: {}", e))?;
     }
     
+    // Initial content must not clobber a note that's already there
+    if let Some(content) = content {
+        if file_path.exists() {
+            return Err(format!("File '{}' already exists", rel));
+        }
+        return write_atomic(&file_path, content.as_bytes())
+            .map_err(|e| format!("Failed to create note: {}", e));
+    }
+    
     // Create the file if it doesn't exist
     if !file_path.exists() {
         fs::write(&file_path, "")
@@ -203,6 +371,19 @@ pub async fn create_note(app_handle: tauri::AppHandle, rel: String) -> Result<()
     Ok(())
 }
 
+#[tauri::command]
+pub async fn create_note(app_handle: tauri::AppHandle, rel: String, content: Option<String>) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+    
+    create_note_file(base_path, &rel, content.as_deref())
+}
+
 #[tauri::command]
 pub async fn create_folder(app_handle: tauri::AppHandle, rel: String) -> Result<(), String> {
     let state = app_handle.state::<std::sync::Mutex<AppState>>();
@@ -242,45 +423,116 @@ pub async fn read_note(app_handle: tauri::AppHandle, rel: String) -> Result<Stri
 }
 
 #[tauri::command]
-pub async fn write_note(app_handle: tauri::AppHandle, rel: String, content: String) -> Result<(), String> {
+pub async fn write_note(app_handle: tauri::AppHandle, rel: String, content: String, force: Option<bool>) -> Result<(), String> {
     let state = app_handle.state::<std::sync::Mutex<AppState>>();
-    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
     
     let base_path = match &state_guard.vault_path {
-        Some(vault_path) => vault_path,
+        Some(vault_path) => vault_path.clone(),
         None => return Err("No vault set".to_string()),
     };
     
     let file_path = base_path.join(&rel);
     
+    check_writable(&file_path, &rel, force.unwrap_or(false))?;
+    
     // Create parent directories if they don't exist
     if let Some(parent) = file_path.parent() {
         fs::create_dir_all(parent)
             .map_err(|e| format!("Failed to create parent directories: {}", e))?;
     }
     
-    fs::write(&file_path, content)
-        .map_err(|e| format!("Failed to write note: {}", e))
+    let config = read_vault_config(&base_path);
+    let content = if config.normalize_on_write {
+        normalize_note_content(&content, config.line_ending)
+    } else {
+        content
+    };
+    
+    write_atomic(&file_path, content.as_bytes())
+        .map_err(|e| format!("Failed to write note: {}", e))?;
+    
+    // Refresh the cached outline so the outline panel doesn't have to ask
+    cache_outline(&mut state_guard.outlines, &rel, &file_path, &content);
+    
+    // The note is saved either way; a missed event only delays the outline panel
+    if let Err(e) = app_handle.emit(OUTLINE_UPDATED_EVENT, &rel) {
+        log::warn!("Saved '{}' but failed to emit outline update: {}", rel, e);
+    }
+    
+    Ok(())
+}
+
+// Strips trailing whitespace from each line, converts line endings to
+// `line_ending` and leaves exactly one newline at the end. Lines inside
+// fenced code blocks keep their trailing whitespace.
+pub(crate) fn normalize_note_content(content: &str, line_ending: LineEnding) -> String {
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+    
+    for line in content.lines() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let trimmed = line.trim_start();
+        let is_fence = trimmed.starts_with("```") || trimmed.starts_with("~~~");
+        
+        if in_fence && !is_fence {
+            lines.push(line);
+        } else {
+            lines.push(line.trim_end());
+        }
+        
+        if is_fence {
+            in_fence = !in_fence;
+        }
+    }
+    
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    
+    if lines.is_empty() {
+        return String::new();
+    }
+    
+    let ending = line_ending.as_str();
+    let mut normalized = lines.join(ending);
+    normalized.push_str(ending);
+    normalized
 }
 
 #[tauri::command]
-pub async fn rename_entry(app_handle: tauri::AppHandle, from: String, to: String) -> Result<(), String> {
+pub async fn rename_entry(app_handle: tauri::AppHandle, from: String, to: String, force: Option<bool>) -> Result<(), String> {
     let state = app_handle.state::<std::sync::Mutex<AppState>>();
-    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
     
     let base_path = match &state_guard.vault_path {
-        Some(vault_path) => vault_path,
+        Some(vault_path) => vault_path.clone(),
         None => return Err("No vault set".to_string()),
     };
     
-    let from_path = base_path.join(&from);
-    let to_path = base_path.join(&to);
+    move_entry(&base_path, &from, &to, force.unwrap_or(false))?;
+    forget_outlines(&mut state_guard.outlines, &from);
+    
+    Ok(())
+}
+
+fn move_entry(base_path: &Path, from: &str, to: &str, force: bool) -> Result<(), String> {
+    let from_path = base_path.join(from);
+    let to_path = base_path.join(to);
     
     if !from_path.exists() {
         return Err(format!("Source path '{}' does not exist", from));
     }
     
-    if to_path.exists() {
+    check_writable(&from_path, from, force)?;
+    
+    // On case-insensitive filesystems `note.md` -> `Note.md` sees the source
+    // as an existing destination; only a real, separately named entry counts
+    let is_case_only_rename = from != to && from.to_lowercase() == to.to_lowercase();
+    
+    let destination_taken = to_path.exists() && (!is_case_only_rename || dir_has_exact_entry(&to_path));
+    
+    if destination_taken {
         return Err(format!("Destination path '{}' already exists", to));
     }
     
@@ -290,17 +542,141 @@ pub async fn rename_entry(app_handle: tauri::AppHandle, from: String, to: String
             .map_err(|e| format!("Failed to create parent directories: {}", e))?;
     }
     
-    fs::rename(&from_path, &to_path)
-        .map_err(|e| format!("Failed to rename entry: {}", e))
+    if is_case_only_rename {
+        rename_via_temp(&from_path, &to_path)?;
+    } else {
+        fs::rename(&from_path, &to_path)
+            .map_err(|e| format!("Failed to rename entry: {}", e))?;
+    }
+    
+    // Keep ids, pins and redirects pointing at the new location
+    log_bookkeeping(&format!("Moved '{}' to '{}'", from, to), [
+        ("id map", ids::record_move(base_path, from, to)),
+        ("pinned folders", ui_state::move_pins(base_path, from, to)),
+        ("redirects", redirects::record_rename(base_path, from, to)),
+    ]);
+    
+    Ok(())
+}
+
+// Sidecar updates (ids, redirects, pins, order files) that run once an entry
+// has already been moved or removed. Their failures are logged rather than
+// returned, so a completed operation is never reported as failed.
+pub(crate) fn log_bookkeeping<const N: usize>(done: &str, results: [(&str, Result<(), String>); N]) {
+    for (what, result) in results {
+        if let Err(e) = result {
+            log::warn!("{} but failed to update {}: {}", done, what, e);
+        }
+    }
+}
+
+// Characters no file name may contain on at least one supported platform.
+pub(crate) const ILLEGAL_NAME_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+// Rejects titles that can't serve as a file name: blank or dot-only names,
+// and ones holding characters some platform forbids.
+pub(crate) fn check_note_title(title: &str) -> Result<(), String> {
+    if title.trim().chars().all(|c| c == '.') {
+        return Err("Title must not be empty".to_string());
+    }
+
+    if let Some(illegal) = title.chars().find(|c| ILLEGAL_NAME_CHARS.contains(c) || c.is_control()) {
+        return Err(format!("Title must not contain '{}'", illegal.escape_default()));
+    }
+
+    Ok(())
+}
+
+// Renames just the name of `rel`, keeping its folder and extension, so
+// "rename title" can't drop the `.md`. Returns the new relative path.
+fn retitle_note(base_path: &Path, rel: &str, new_title: &str) -> Result<String, String> {
+    let title = new_title.trim();
+    check_note_title(title)?;
+    
+    let from = Path::new(rel);
+    let file_name = match from.extension() {
+        Some(ext) => format!("{}.{}", title, ext.to_string_lossy()),
+        None => title.to_string(),
+    };
+    let to = from.with_file_name(&file_name).to_string_lossy().to_string();
+    
+    if to == rel {
+        return Ok(to);
+    }
+    
+    move_entry(base_path, rel, &to, false)?;
+    
+    // The renamed note keeps its place in a custom order
+    if let (Some(dir), Some(old_name)) = (base_path.join(rel).parent(), from.file_name()) {
+        log_bookkeeping(&format!("Renamed '{}' to '{}'", rel, to), [
+            ("order file", ordering::rename(dir, &old_name.to_string_lossy(), &file_name)),
+        ]);
+    }
+    
+    Ok(to)
+}
+
+#[tauri::command]
+pub async fn rename_note_title(app_handle: tauri::AppHandle, rel: String, new_title: String) -> Result<String, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+    
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path.clone(),
+        None => return Err("No vault set".to_string()),
+    };
+    
+    let to = retitle_note(&base_path, &rel, &new_title)?;
+    if to != rel {
+        forget_outlines(&mut state_guard.outlines, &rel);
+    }
+    
+    Ok(to)
+}
+
+// Whether the parent directory lists an entry with exactly this file name,
+// as opposed to one that merely matches case-insensitively.
+fn dir_has_exact_entry(path: &Path) -> bool {
+    let (parent, name) = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => (parent, name),
+        _ => return false,
+    };
+    
+    match fs::read_dir(parent) {
+        Ok(entries) => entries.flatten().any(|entry| entry.file_name() == name),
+        Err(_) => false,
+    }
+}
+
+// Two-step rename through a temporary sibling name, for renames that only
+// change letter case and that some filesystems treat as a no-op.
+fn rename_via_temp(from_path: &Path, to_path: &Path) -> Result<(), String> {
+    let file_name = from_path
+        .file_name()
+        .ok_or("Failed to get file name")?
+        .to_string_lossy()
+        .to_string();
+    let temp_path = from_path.with_file_name(format!(".{}.tau_rename.tmp", file_name));
+    
+    fs::rename(from_path, &temp_path)
+        .map_err(|e| format!("Failed to rename entry: {}", e))?;
+    
+    if let Err(e) = fs::rename(&temp_path, to_path) {
+        // Put the original back rather than leaving the temp name behind
+        let _ = fs::rename(&temp_path, from_path);
+        return Err(format!("Failed to rename entry: {}", e));
+    }
+    
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn delete_entry(app_handle: tauri::AppHandle, rel: String) -> Result<(), String> {
     let state = app_handle.state::<std::sync::Mutex<AppState>>();
-    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
     
     let base_path = match &state_guard.vault_path {
-        Some(vault_path) => vault_path,
+        Some(vault_path) => vault_path.clone(),
         None => return Err("No vault set".to_string()),
     };
     
@@ -310,13 +686,29 @@ pub async fn delete_entry(app_handle: tauri::AppHandle, rel: String) -> Result<(
         return Err(format!("Path '{}' does not exist", rel));
     }
     
-    if target_path.is_dir() {
+    // Notes and folders go to the trash (folders whole, order files
+    // included) so restore_from_trash can bring them back; anything already
+    // in the trash is deleted for good
+    let rel_path = sanitize_relative_path(&rel)?;
+    let in_trash = rel_path.starts_with(TRASH_DIR);
+    
+    if !in_trash {
+        move_to_trash(&base_path, &rel_path)?;
+    } else if target_path.is_dir() {
         fs::remove_dir_all(&target_path)
-            .map_err(|e| format!("Failed to delete directory: {}", e))
+            .map_err(|e| format!("Failed to delete directory: {}", e))?;
     } else {
         fs::remove_file(&target_path)
-            .map_err(|e| format!("Failed to delete file: {}", e))
+            .map_err(|e| format!("Failed to delete file: {}", e))?;
     }
+    
+    forget_outlines(&mut state_guard.outlines, &rel);
+    log_bookkeeping(&format!("Deleted '{}'", rel), [
+        ("pinned folders", ui_state::unpin_removed(&base_path, &rel)),
+        ("id map", ids::record_removal(&base_path, &rel)),
+    ]);
+    
+    Ok(())
 }
 
 #[tauri::command]
@@ -362,6 +754,31 @@ pub async fn reveal_in_os(app_handle: tauri::AppHandle, rel: String) -> Result<(
     Ok(())
 }
 
+// Names in a directory in their current custom order; entries missing from
+// the order file follow alphabetically. App-managed files are left out.
+fn read_ordered_names(target_dir: &Path) -> Result<Vec<String>, String> {
+    // Read current entries to get all file names
+    let dir_entries = fs::read_dir(target_dir)
+        .map_err(|e| format!("Failed to read directory: {}", e))?;
+
+    let mut file_names = Vec::new();
+    for entry in dir_entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // Skip .tau_order.json and other internal files
+        if !is_internal_name(&name) {
+            file_names.push(name);
+        }
+    }
+
+    // Sort by current order if it exists
+    let current_order = ordering::load(target_dir);
+    file_names.sort_by(|a, b| current_order.compare(a, b));
+
+    Ok(file_names)
+}
+
 #[tauri::command]
 pub async fn reorder_entries(app_handle: tauri::AppHandle, dir_path: Option<String>, source: String, target: String, position: String) -> Result<(), String> {
     let state = app_handle.state::<std::sync::Mutex<AppState>>();
@@ -382,34 +799,7 @@ pub async fn reorder_entries(app_handle: tauri::AppHandle, dir_path: Option<Stri
         return Err(format!("Directory '{}' does not exist", target_dir.display()));
     }
 
-    // Read current entries to get all file names
-    let dir_entries = fs::read_dir(&target_dir)
-        .map_err(|e| format!("Failed to read directory: {}", e))?;
-
-    let mut file_names = Vec::new();
-    for entry in dir_entries {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let name = entry.file_name().to_string_lossy().to_string();
-
-        // Skip .tau_order.json files
-        if !name.ends_with(".tau_order.json") {
-            file_names.push(name);
-        }
-    }
-
-    // Sort by current order if it exists
-    let current_order = read_file_order(&target_dir);
-    file_names.sort_by(|a, b| {
-        let a_order = current_order.get(a);
-        let b_order = current_order.get(b);
-
-        match (a_order, b_order) {
-            (Some(a_idx), Some(b_idx)) => a_idx.cmp(b_idx),
-            (Some(_), None) => std::cmp::Ordering::Less,
-            (None, Some(_)) => std::cmp::Ordering::Greater,
-            (None, None) => a.to_lowercase().cmp(&b.to_lowercase()),
-        }
-    });
+    let mut file_names = read_ordered_names(&target_dir)?;
 
     // Extract just the filename from source path
     let source_name = source.split(['/', '\\']).last().unwrap_or(&source).to_string();
@@ -434,7 +824,7 @@ pub async fn reorder_entries(app_handle: tauri::AppHandle, dir_path: Option<Stri
             file_names.insert(insert_idx, source_item);
 
             // Write the new order
-            write_file_order(&target_dir, file_names)?;
+            ordering::save(&target_dir, file_names)?;
         }
         _ => {
             return Err(format!("Could not find source '{}' or target '{}' in directory", source_name, target_name));
@@ -444,22 +834,487 @@ pub async fn reorder_entries(app_handle: tauri::AppHandle, dir_path: Option<Stri
     Ok(())
 }
 
-// Link parsing functions
-fn parse_links_from_content(content: &str, source_file: &str) -> Vec<Link> {
-    let mut links = Vec::new();
-    let link_regex = Regex::new(r"\[\[([^\[\]]+)\]\]").unwrap();
-    
-    for mat in link_regex.find_iter(content) {
-        let _full_match = mat.as_str();
-        let link_content = &content[mat.start() + 2..mat.end() - 2]; // Remove [[ and ]]
-        
-        let (target_note, display_text) = if let Some(pipe_pos) = link_content.find('|') {
-            let note = &link_content[..pipe_pos];
-            let display = &link_content[pipe_pos + 1..];
-            (note.to_string(), Some(display.to_string()))
-        } else {
-            (link_content.to_string(), None)
-        };
+// Multi-selection version of `reorder_entries`: every source moves next to
+// `target` as one block, keeping the order the sources had among themselves.
+#[tauri::command]
+pub async fn reorder_multiple(app_handle: tauri::AppHandle, dir_path: Option<String>, sources: Vec<String>, target: String, position: String) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let target_dir = match dir_path {
+        Some(rel_path) => base_path.join(sanitize_relative_path(&rel_path)?),
+        None => base_path.clone(),
+    };
+
+    if !target_dir.is_dir() {
+        return Err(format!("Directory '{}' does not exist", target_dir.display()));
+    }
+
+    move_block(&target_dir, &sources, &target, &position)
+}
+
+fn move_block(target_dir: &Path, sources: &[String], target: &str, position: &str) -> Result<(), String> {
+    let file_names = read_ordered_names(target_dir)?;
+
+    let source_names: HashSet<String> = sources
+        .iter()
+        .map(|source| source.split(['/', '\\']).last().unwrap_or(source).to_string())
+        .collect();
+    let target_name = target.split(['/', '\\']).last().unwrap_or(target).to_string();
+
+    if let Some(missing) = source_names.iter().find(|name| !file_names.contains(name)) {
+        return Err(format!("Could not find source '{}' in directory", missing));
+    }
+
+    if source_names.contains(&target_name) {
+        return Err(format!("Target '{}' is part of the selection", target_name));
+    }
+
+    let (selected, mut remaining): (Vec<String>, Vec<String>) = file_names
+        .into_iter()
+        .partition(|name| source_names.contains(name));
+
+    let target_idx = match remaining.iter().position(|name| name == &target_name) {
+        Some(idx) => idx,
+        None => return Err(format!("Could not find target '{}' in directory", target_name)),
+    };
+
+    let insert_idx = if position == "before" { target_idx } else { target_idx + 1 };
+    remaining.splice(insert_idx..insert_idx, selected);
+
+    ordering::save(target_dir, remaining)
+}
+
+// Out-of-range indices place the entry last.
+#[tauri::command]
+pub async fn move_to_position(app_handle: tauri::AppHandle, dir_path: Option<String>, name: String, index: usize) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let target_dir = match dir_path {
+        Some(rel_path) => base_path.join(sanitize_relative_path(&rel_path)?),
+        None => base_path.clone(),
+    };
+
+    if !target_dir.is_dir() {
+        return Err(format!("Directory '{}' does not exist", target_dir.display()));
+    }
+
+    place_at_index(&target_dir, &name, index)
+}
+
+fn place_at_index(target_dir: &Path, name: &str, index: usize) -> Result<(), String> {
+    let mut file_names = read_ordered_names(target_dir)?;
+
+    let current_idx = file_names
+        .iter()
+        .position(|file_name| file_name == name)
+        .ok_or_else(|| format!("Could not find '{}' in directory", name))?;
+
+    let entry = file_names.remove(current_idx);
+    let insert_idx = index.min(file_names.len());
+    file_names.insert(insert_idx, entry);
+
+    ordering::save(target_dir, file_names)
+}
+
+pub(crate) fn relative_path_string(base_path: &Path, path: &Path) -> Result<String, String> {
+    Ok(path
+        .strip_prefix(base_path)
+        .map_err(|e| format!("Failed to create relative path: {}", e))?
+        .to_string_lossy()
+        .to_string())
+}
+
+// Writes through a temporary sibling and renames it into place, so readers
+// never observe a half-written file.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{}.tau_write.tmp", file_name));
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+// First free path for `file_name` inside `dir`, appending ` 1`, ` 2`, ...
+// to the stem when the name is already taken.
+pub(crate) fn unique_destination(dir: &Path, file_name: &str) -> PathBuf {
+    let candidate = dir.join(file_name);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let (stem, ext) = match file_name.rfind('.') {
+        Some(pos) if pos > 0 => (&file_name[..pos], &file_name[pos..]),
+        _ => (file_name, ""),
+    };
+
+    let mut n = 1;
+    loop {
+        let candidate = dir.join(format!("{} {}{}", stem, n, ext));
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+// App-managed files such as `.tau_order.json` and `.tau_lock`.
+pub(crate) fn is_internal_name(name: &str) -> bool {
+    name.starts_with(".tau_")
+}
+
+// Recursively collects every file under `dir`, sorted so callers get stable
+// output. Hidden directories (.git, .trash, ...) and internal files are
+// skipped.
+pub(crate) fn collect_vault_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+        let entries = fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read directory: {}", e))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if path.is_dir() {
+                if !name.starts_with('.') {
+                    walk(&path, files)?;
+                }
+            } else if !is_internal_name(&name) {
+                files.push(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    walk(dir, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+// Every relative path in the vault, sorted. Unless `include_hidden` is set,
+// hidden folders such as .trash and internal files are skipped like they are
+// everywhere else.
+#[tauri::command]
+pub async fn all_paths(app_handle: tauri::AppHandle, include_files: bool, include_dirs: bool, include_hidden: Option<bool>) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    vault_paths(base_path, include_files, include_dirs, include_hidden.unwrap_or(false))
+}
+
+fn vault_paths(base_path: &Path, include_files: bool, include_dirs: bool, include_hidden: bool) -> Result<Vec<String>, String> {
+    struct Walk<'a> {
+        base_path: &'a Path,
+        include_files: bool,
+        include_dirs: bool,
+        include_hidden: bool,
+    }
+
+    fn walk(options: &Walk, dir: &Path, paths: &mut Vec<String>) -> Result<(), String> {
+        let entries = fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read directory: {}", e))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if path.is_dir() {
+                if name.starts_with('.') && !options.include_hidden {
+                    continue;
+                }
+                if options.include_dirs {
+                    paths.push(relative_path_string(options.base_path, &path)?);
+                }
+                walk(options, &path, paths)?;
+            } else if options.include_files && (options.include_hidden || !is_internal_name(&name)) {
+                paths.push(relative_path_string(options.base_path, &path)?);
+            }
+        }
+
+        Ok(())
+    }
+
+    let options = Walk {
+        base_path,
+        include_files,
+        include_dirs,
+        include_hidden,
+    };
+
+    let mut paths = Vec::new();
+    walk(&options, base_path, &mut paths)?;
+    paths.sort();
+
+    Ok(paths)
+}
+
+pub(crate) fn is_markdown_file(path: &Path) -> bool {
+    path.extension().and_then(|s| s.to_str()) == Some("md")
+}
+
+pub(crate) fn collect_markdown_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    Ok(collect_vault_files(dir)?
+        .into_iter()
+        .filter(|path| is_markdown_file(path))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn list_extensions(app_handle: tauri::AppHandle) -> Result<Vec<ExtCount>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for path in collect_vault_files(base_path)? {
+        // Files without an extension are grouped under ""
+        let ext = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        *counts.entry(ext).or_insert(0) += 1;
+    }
+
+    let mut extensions: Vec<ExtCount> = counts
+        .into_iter()
+        .map(|(ext, count)| ExtCount { ext, count })
+        .collect();
+    extensions.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.ext.cmp(&b.ext)));
+
+    Ok(extensions)
+}
+
+// Notes of at least `min_bytes`, largest first. Other files such as
+// attachments are only included with `include_non_markdown`.
+fn files_at_least(base_path: &Path, min_bytes: u64, include_non_markdown: bool) -> Result<Vec<Entry>, String> {
+    let files = if include_non_markdown {
+        collect_vault_files(base_path)?
+    } else {
+        collect_markdown_files(base_path)?
+    };
+
+    let mut large = Vec::new();
+
+    for path in files {
+        let metadata = fs::metadata(&path)
+            .map_err(|e| format!("Failed to read metadata: {}", e))?;
+        if metadata.len() >= min_bytes {
+            large.push(build_entry(base_path, &path, &metadata)?);
+        }
+    }
+
+    large.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+
+    Ok(large)
+}
+
+#[tauri::command]
+pub async fn large_notes(app_handle: tauri::AppHandle, min_bytes: u64, include_non_markdown: Option<bool>) -> Result<Vec<Entry>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    files_at_least(base_path, min_bytes, include_non_markdown.unwrap_or(false))
+}
+
+fn disk_usage(base_path: &Path) -> Result<DiskUsage, String> {
+    // `internal` is set once the walk enters a hidden folder such as .git,
+    // whose contents count as metadata rather than notes or attachments
+    fn walk(dir: &Path, internal: bool, trash: bool, usage: &mut DiskUsage) -> Result<(), String> {
+        let entries = fs::read_dir(dir)
+            .map_err(|e| format!("Failed to read directory: {}", e))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let metadata = entry
+                .metadata()
+                .map_err(|e| format!("Failed to read metadata: {}", e))?;
+
+            if metadata.is_dir() {
+                let is_trash = trash || name == TRASH_DIR;
+                walk(&entry.path(), internal || (!is_trash && name.starts_with('.')), is_trash, usage)?;
+                continue;
+            }
+
+            let size = metadata.len();
+            usage.total_bytes += size;
+
+            if trash {
+                usage.trash_bytes += size;
+            } else if internal || is_internal_name(&name) {
+                usage.internal_bytes += size;
+            } else if is_markdown_file(&entry.path()) {
+                usage.markdown_bytes += size;
+            } else {
+                usage.attachment_bytes += size;
+            }
+        }
+
+        Ok(())
+    }
+
+    let mut usage = DiskUsage::default();
+    walk(base_path, false, false, &mut usage)?;
+
+    Ok(usage)
+}
+
+#[tauri::command]
+pub async fn vault_disk_usage(app_handle: tauri::AppHandle) -> Result<DiskUsage, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    disk_usage(base_path)
+}
+
+// Matches a vault-relative path against a glob: `*` and `?` stay within one
+// path segment, `**` spans any number of them.
+pub(crate) fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.replace('\\', "/");
+    let mut chars = pattern.chars().peekable();
+    let mut regex = String::from("^");
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` also matches no folder at all
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+
+    match Regex::new(&regex) {
+        Ok(glob_regex) => glob_regex.is_match(&path.replace('\\', "/")),
+        Err(_) => false,
+    }
+}
+
+const IGNORE_FILE_NAME: &str = ".tauignore";
+
+// Glob patterns from the vault's `.tauignore`, one per line. Blank lines and
+// `#` comments are skipped, and a trailing `/` is dropped so `drafts/` and
+// `drafts` mean the same folder.
+pub(crate) fn read_ignore_patterns(base_path: &Path) -> Vec<String> {
+    fs::read_to_string(base_path.join(IGNORE_FILE_NAME))
+        .unwrap_or_default()
+        .lines()
+        .map(|line| line.trim().trim_end_matches('/'))
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect()
+}
+
+// Whether `rel`, or any folder it sits in, matches one of `patterns`.
+pub(crate) fn is_ignored(patterns: &[String], rel: &str) -> bool {
+    let rel = rel.replace('\\', "/");
+    let mut prefixes = rel.match_indices('/').map(|(i, _)| &rel[..i]).collect::<Vec<_>>();
+    prefixes.push(&rel);
+
+    patterns
+        .iter()
+        .any(|pattern| prefixes.iter().any(|prefix| glob_matches(pattern, prefix)))
+}
+
+// Entries (with full metadata) for every markdown note in the vault.
+pub(crate) fn collect_note_entries(base_path: &Path) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    
+    for path in collect_markdown_files(base_path)? {
+        let metadata = fs::metadata(&path)
+            .map_err(|e| format!("Failed to read metadata: {}", e))?;
+        entries.push(build_entry(base_path, &path, &metadata)?);
+    }
+    
+    Ok(entries)
+}
+
+// Runs `scan` over a note's text. Invalid UTF-8 is replaced rather than
+// treated as an error, which only costs a copy when the file actually
+// contains bad bytes. Notes are read rather than memory-mapped, since a sync
+// tool truncating a mapped note mid-scan would crash the app with SIGBUS.
+pub(crate) fn with_note_content<T, F>(path: &Path, scan: F) -> Result<T, String>
+where
+    F: Fn(&str) -> T,
+{
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    Ok(scan(&String::from_utf8_lossy(&bytes)))
+}
+
+// Link parsing functions
+pub(crate) fn parse_links_from_content(content: &str, source_file: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let link_regex = Regex::new(r"\[\[([^\[\]]+)\]\]").unwrap();
+    
+    for mat in link_regex.find_iter(content) {
+        let _full_match = mat.as_str();
+        let link_content = &content[mat.start() + 2..mat.end() - 2]; // Remove [[ and ]]
+        
+        let (target_note, display_text) = if let Some(pipe_pos) = link_content.find('|') {
+            let note = &link_content[..pipe_pos];
+            let display = &link_content[pipe_pos + 1..];
+            (note.to_string(), Some(display.to_string()))
+        } else {
+            (link_content.to_string(), None)
+        };
         
         links.push(Link {
             source_file: source_file.to_string(),
@@ -526,10 +1381,9 @@ pub async fn get_all_links(app_handle: tauri::AppHandle) -> Result<Vec<Link>, St
                     .to_string_lossy()
                     .to_string();
                 
-                let content = fs::read_to_string(&path)
-                    .map_err(|e| format!("Failed to read file: {}", e))?;
-                
-                let file_links = parse_links_from_content(&content, &relative_path);
+                let file_links = with_note_content(&path, |content| {
+                    parse_links_from_content(content, &relative_path)
+                })?;
                 links.extend(file_links);
             }
         }
@@ -537,8 +1391,27 @@ pub async fn get_all_links(app_handle: tauri::AppHandle) -> Result<Vec<Link>, St
         Ok(())
     }
     
-    walk_dir(base_path, base_path, &mut all_links)?;
-    Ok(all_links)
+    timed!("get_all_links", base_path.display(), {
+        walk_dir(base_path, base_path, &mut all_links)?;
+        Ok(all_links)
+    })
+}
+
+// Simple similarity function (can be enhanced later)
+pub(crate) fn calculate_similarity(query: &str, note_name: &str) -> f64 {
+    let query_lower = query.to_lowercase();
+    let note_lower = note_name.to_lowercase();
+    
+    if note_lower.contains(&query_lower) {
+        return 1.0 - (query_lower.len() as f64 / note_lower.len() as f64) * 0.5;
+    }
+    
+    // Simple character-based similarity
+    let common_chars = query_lower.chars()
+        .filter(|c| note_lower.contains(*c))
+        .count();
+    
+    common_chars as f64 / query_lower.len().max(note_lower.len()) as f64
 }
 
 #[tauri::command]
@@ -554,23 +1427,6 @@ pub async fn suggest_links(app_handle: tauri::AppHandle, query: String) -> Resul
     let mut suggestions = Vec::new();
     let _query_lower = query.to_lowercase();
     
-    // Simple similarity function (can be enhanced later)
-    fn calculate_similarity(query: &str, note_name: &str) -> f64 {
-        let query_lower = query.to_lowercase();
-        let note_lower = note_name.to_lowercase();
-        
-        if note_lower.contains(&query_lower) {
-            return 1.0 - (query_lower.len() as f64 / note_lower.len() as f64) * 0.5;
-        }
-        
-        // Simple character-based similarity
-        let common_chars = query_lower.chars()
-            .filter(|c| note_lower.contains(*c))
-            .count();
-        
-        common_chars as f64 / query_lower.len().max(note_lower.len()) as f64
-    }
-    
     // Walk through all markdown files to find potential matches
     fn find_notes(dir: &std::path::Path, base_path: &std::path::Path, query: &str, suggestions: &mut Vec<LinkSuggestion>) -> Result<(), String> {
         let entries = fs::read_dir(dir)
@@ -618,3 +1474,346 @@ pub async fn suggest_links(app_handle: tauri::AppHandle, query: String) -> Resul
     suggestions.truncate(10);
     Ok(suggestions)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    fn names(entries: &[Entry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    // A note of at least `min_bytes` holding one link per line
+    fn large_note(min_bytes: usize) -> String {
+        let line = "Some text with a [[Target]] link in it.\n";
+        line.repeat(min_bytes / line.len() + 1)
+    }
+
+    #[test]
+    fn small_note_with_invalid_utf8_is_read_lossily() {
+        let vault = TempVault::new();
+        let path = vault.write("bad.md", b"before \xff\xfe [[Target]] after");
+
+        let content = with_note_content(&path, |content| content.to_string()).unwrap();
+        assert_eq!(content, "before \u{FFFD}\u{FFFD} [[Target]] after");
+        assert_eq!(with_note_content(&path, |content| parse_links_from_content(content, "bad.md").len()).unwrap(), 1);
+    }
+
+    #[test]
+    fn large_note_with_invalid_utf8_is_read_lossily() {
+        let vault = TempVault::new();
+        let mut bytes = large_note(256 * 1024).into_bytes();
+        bytes.extend_from_slice(b"\xc3\x28 [[Last]]\n");
+        let path = vault.write("big.md", &bytes);
+
+        let (links, replaced) = with_note_content(&path, |content| {
+            (parse_links_from_content(content, "big.md"), content.matches('\u{FFFD}').count())
+        })
+        .unwrap();
+
+        assert_eq!(replaced, 1);
+        assert_eq!(links.last().unwrap().target_note, "Last");
+    }
+
+    #[test]
+    fn rename_updates_id_map() {
+        let vault = TempVault::new();
+        vault.write("Old.md", "---\nid: note-1\n---\n");
+        vault.write("Folder/Inner.md", "---\nid: note-2\n---\n");
+        vault.write(".tau_ids.json", r#"{"ids":{"note-1":"Old.md","note-2":"Folder/Inner.md"}}"#);
+
+        move_entry(vault.path(), "Old.md", "Sub/New.md", false).unwrap();
+        move_entry(vault.path(), "Folder", "Renamed", false).unwrap();
+
+        let ids = ids::read_id_map(vault.path()).ids;
+        assert_eq!(ids["note-1"], "Sub/New.md");
+        assert_eq!(ids["note-2"], "Renamed/Inner.md");
+    }
+
+    #[test]
+    fn failed_bookkeeping_does_not_fail_the_move() {
+        let vault = TempVault::new();
+        vault.write("Old.md", "# Old");
+        // A folder in place of the redirect file makes recording the rename fail
+        fs::create_dir(vault.path().join(".tau_redirects.json")).unwrap();
+
+        move_entry(vault.path(), "Old.md", "New.md", false).unwrap();
+        assert!(vault.path().join("New.md").is_file());
+        assert!(!vault.path().join("Old.md").exists());
+    }
+
+    #[test]
+    fn retitle_keeps_folder_extension_and_order() {
+        let vault = TempVault::new();
+        vault.write("a/b.md", "# B");
+        vault.write("a/other.md", "");
+        vault.write("a/.tau_order.json", r#"{"order":["b.md","other.md"]}"#);
+
+        assert_eq!(retitle_note(vault.path(), "a/b.md", " c ").unwrap(), "a/c.md");
+        assert_eq!(vault.read("a/c.md"), "# B");
+        assert!(!vault.path().join("a/b.md").exists());
+        assert_eq!(ordering::load(&vault.path().join("a")).names(), vec!["c.md", "other.md"]);
+    }
+
+    #[test]
+    fn missing_note_is_an_error() {
+        let vault = TempVault::new();
+        assert!(with_note_content(&vault.path().join("missing.md"), |content| content.len()).is_err());
+    }
+
+    #[test]
+    fn consecutive_pages_neither_overlap_nor_skip() {
+        let vault = TempVault::new();
+        for name in ["e.md", "a.md", "d.md", "Sub/x.md", "b.md", "c.md"] {
+            vault.write(name, "");
+        }
+        vault.write(".tau_order.json", r#"{"order":["d.md","a.md"]}"#);
+
+        let all = read_sorted_entries(vault.path(), vault.path(), false).unwrap();
+        let first = paginate(read_sorted_entries(vault.path(), vault.path(), false).unwrap(), Some(0), Some(4));
+        let second = paginate(read_sorted_entries(vault.path(), vault.path(), false).unwrap(), Some(4), Some(4));
+
+        assert_eq!((first.total, second.total), (6, 6));
+        assert_eq!(names(&first.entries), vec!["Sub", "d.md", "a.md", "b.md"]);
+        assert_eq!(names(&second.entries), vec!["c.md", "e.md"]);
+
+        let mut joined = names(&first.entries);
+        joined.extend(names(&second.entries));
+        assert_eq!(joined, names(&all));
+        assert_eq!(names(&paginate(all, None, None).entries), joined);
+    }
+
+    #[test]
+    fn case_only_rename_goes_through_a_temp_name() {
+        let vault = TempVault::new();
+        vault.write("note.md", "---\nid: note-1\n---\nbody");
+        vault.write(".tau_ids.json", r#"{"ids":{"note-1":"note.md"}}"#);
+
+        move_entry(vault.path(), "note.md", "Note.md", false).unwrap();
+
+        assert!(dir_has_exact_entry(&vault.path().join("Note.md")));
+        assert!(!dir_has_exact_entry(&vault.path().join("note.md")));
+        assert_eq!(vault.read("Note.md"), "---\nid: note-1\n---\nbody");
+        assert_eq!(ids::read_id_map(vault.path()).ids["note-1"], "Note.md");
+
+        let leftovers: Vec<String> = fs::read_dir(vault.path())
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name.ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[test]
+    fn case_only_rename_still_refuses_a_real_second_entry() {
+        let vault = TempVault::new();
+        vault.write("note.md", "lower");
+        vault.write("Note.md", "upper");
+
+        // Only on a case-sensitive filesystem can both names exist at once
+        if dir_has_exact_entry(&vault.path().join("note.md")) && dir_has_exact_entry(&vault.path().join("Note.md")) {
+            assert!(move_entry(vault.path(), "note.md", "Note.md", false).is_err());
+            assert_eq!(vault.read("Note.md"), "upper");
+        }
+    }
+
+    #[test]
+    fn failed_second_step_restores_the_original_name() {
+        let vault = TempVault::new();
+        let from = vault.write("note.md", "body");
+
+        // A missing parent folder makes the move out of the temp name fail
+        assert!(rename_via_temp(&from, &vault.path().join("missing/Note.md")).is_err());
+        assert_eq!(vault.read("note.md"), "body");
+    }
+
+    #[test]
+    fn disk_usage_is_broken_down_by_kind() {
+        let vault = TempVault::new();
+        vault.write("a.md", "12345");
+        vault.write("Sub/b.md", "123");
+        vault.write("Sub/img.png", "1234567890");
+        vault.write(".tau_order.json", "1234567");
+        vault.write(".git/HEAD", "1234");
+        vault.write(".git/objects/ab.md", "12");
+        vault.write(".trash/old.md", "123456");
+        vault.write(".trash/Folder/pic.png", "1");
+
+        let usage = disk_usage(vault.path()).unwrap();
+
+        assert_eq!(usage.markdown_bytes, 8);
+        assert_eq!(usage.attachment_bytes, 10);
+        assert_eq!(usage.internal_bytes, 13);
+        assert_eq!(usage.trash_bytes, 7);
+        assert_eq!(usage.total_bytes, 38);
+    }
+
+    #[test]
+    fn creates_notes_with_and_without_content() {
+        let vault = TempVault::new();
+
+        create_note_file(vault.path(), "Empty.md", None).unwrap();
+        create_note_file(vault.path(), "New/Deep/Filled.md", Some("# Filled\n")).unwrap();
+
+        assert_eq!(vault.read("Empty.md"), "");
+        assert_eq!(vault.read("New/Deep/Filled.md"), "# Filled\n");
+
+        // Without content an existing note is left alone; with content it's an error
+        vault.write("Existing.md", "keep");
+        create_note_file(vault.path(), "Existing.md", None).unwrap();
+        assert!(create_note_file(vault.path(), "Existing.md", Some("clobber")).is_err());
+        assert_eq!(vault.read("Existing.md"), "keep");
+    }
+
+    #[test]
+    fn moves_to_the_front_and_clamps_past_the_end() {
+        let vault = TempVault::new();
+        for name in ["a.md", "b.md", "c.md", "d.md"] {
+            vault.write(name, "");
+        }
+        let order = || ordering::load(vault.path()).names();
+
+        place_at_index(vault.path(), "c.md", 0).unwrap();
+        assert_eq!(order(), vec!["c.md", "a.md", "b.md", "d.md"]);
+
+        place_at_index(vault.path(), "a.md", 99).unwrap();
+        assert_eq!(order(), vec!["c.md", "b.md", "d.md", "a.md"]);
+
+        assert!(place_at_index(vault.path(), "missing.md", 0).is_err());
+    }
+
+    #[test]
+    fn all_paths_honours_each_flag_combination() {
+        let vault = TempVault::new();
+        vault.write("a.md", "");
+        vault.write("Sub/b.md", "");
+        vault.write("Sub/Deep/c.png", "");
+        vault.write(".tau_order.json", "{}");
+        vault.write(".trash/old.md", "");
+
+        let paths = |files, dirs| vault_paths(vault.path(), files, dirs, false).unwrap();
+
+        assert_eq!(paths(true, true), vec!["Sub", "Sub/Deep", "Sub/Deep/c.png", "Sub/b.md", "a.md"]);
+        assert_eq!(paths(true, false), vec!["Sub/Deep/c.png", "Sub/b.md", "a.md"]);
+        assert_eq!(paths(false, true), vec!["Sub", "Sub/Deep"]);
+        assert!(paths(false, false).is_empty());
+
+        assert_eq!(
+            vault_paths(vault.path(), true, true, true).unwrap(),
+            vec![".tau_order.json", ".trash", ".trash/old.md", "Sub", "Sub/Deep", "Sub/Deep/c.png", "Sub/b.md", "a.md"]
+        );
+    }
+
+    #[test]
+    fn large_notes_include_exactly_min_bytes() {
+        let vault = TempVault::new();
+        vault.write("under.md", "x".repeat(99));
+        vault.write("exact.md", "x".repeat(100));
+        vault.write("Sub/over.md", "x".repeat(250));
+        vault.write("image.png", "x".repeat(500));
+
+        let paths = |include_non_markdown| -> Vec<(String, u64)> {
+            files_at_least(vault.path(), 100, include_non_markdown)
+                .unwrap()
+                .into_iter()
+                .map(|entry| (entry.path, entry.size))
+                .collect()
+        };
+
+        assert_eq!(paths(false), vec![("Sub/over.md".to_string(), 250), ("exact.md".to_string(), 100)]);
+        assert_eq!(paths(true)[0], ("image.png".to_string(), 500));
+        assert_eq!(paths(true).len(), 3);
+    }
+
+    #[test]
+    fn position_follows_custom_order_then_alphabetical() {
+        let vault = TempVault::new();
+        for name in ["a.md", "b.md", "c.md", "d.md", "Folder/x.md", "Other/y.md"] {
+            vault.write(name, "");
+        }
+        vault.write(".tau_order.json", r#"{"order":["c.md","Other"]}"#);
+
+        let position = |rel: &str| {
+            let found = folder_position(vault.path(), &vault.path().join(rel), rel).unwrap();
+            (found.index, found.total)
+        };
+
+        // c.md is ranked by the order file; the rest fall back to sorting by name
+        assert_eq!(position("c.md"), (0, 4));
+        assert_eq!(position("b.md"), (2, 4));
+        // Folders are counted separately from files
+        assert_eq!(position("Other"), (0, 2));
+        assert_eq!(position("Folder"), (1, 2));
+        assert_eq!(position("Folder/x.md"), (0, 1));
+    }
+
+    #[test]
+    fn effective_order_matches_the_listing() {
+        let vault = TempVault::new();
+        for name in ["b.md", "A.md", "c.png", "Zed/x.md", "Alpha/y.md", "beta/z.md"] {
+            vault.write(name, "");
+        }
+        vault.write(".tau_order.json", r#"{"order":["c.png","Zed"]}"#);
+
+        for include_hidden in [false, true] {
+            let listed = read_sorted_entries(vault.path(), vault.path(), include_hidden).unwrap();
+            assert_eq!(listed_names(vault.path(), vault.path(), include_hidden).unwrap(), names(&listed));
+        }
+        assert_eq!(
+            listed_names(vault.path(), vault.path(), false).unwrap(),
+            vec!["Zed", "Alpha", "beta", "c.png", "A.md", "b.md"]
+        );
+    }
+
+    #[test]
+    fn listing_hides_internal_files_unless_asked() {
+        let vault = TempVault::new();
+        vault.write("note.md", "");
+        vault.write(".tau_order.json", r#"{"order":["note.md"]}"#);
+        vault.write(".trash/old.md", "");
+
+        let listed = |include_hidden| {
+            let mut listed: Vec<String> = read_sorted_entries(vault.path(), vault.path(), include_hidden)
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.name)
+                .collect();
+            listed.sort();
+            listed
+        };
+
+        assert_eq!(listed(false), vec!["note.md"]);
+        assert_eq!(listed(true), vec![".tau_order.json", ".trash", "note.md"]);
+    }
+
+    #[test]
+    fn multi_move_keeps_the_selection_in_order() {
+        let vault = TempVault::new();
+        for name in ["a.md", "b.md", "c.md", "d.md", "e.md", "f.md"] {
+            vault.write(name, "");
+        }
+
+        // b and c are adjacent, f is further down; the selection order doesn't matter
+        let sources = vec!["f.md".to_string(), "Sub/b.md".to_string(), "c.md".to_string()];
+        move_block(vault.path(), &sources, "a.md", "before").unwrap();
+        assert_eq!(ordering::load(vault.path()).names(), vec!["b.md", "c.md", "f.md", "a.md", "d.md", "e.md"]);
+
+        move_block(vault.path(), &sources, "e.md", "after").unwrap();
+        assert_eq!(ordering::load(vault.path()).names(), vec!["a.md", "d.md", "e.md", "b.md", "c.md", "f.md"]);
+
+        assert!(move_block(vault.path(), &sources, "c.md", "before").is_err());
+    }
+
+    #[test]
+    fn normalizing_keeps_code_fences_verbatim() {
+        let content = "# Title   \r\n\r\n```diff\r\n- old  \r\n+ new\t\r\n```  \r\ntext \t\r\n~~~\r\nkeep  \r\n~~~\r\n\r\n\r\n";
+
+        assert_eq!(
+            normalize_note_content(content, LineEnding::Lf),
+            "# Title\n\n```diff\n- old  \n+ new\t\n```\ntext\n~~~\nkeep  \n~~~\n"
+        );
+        assert_eq!(normalize_note_content("a  \n```\nb  \n", LineEnding::Crlf), "a\r\n```\r\nb  \r\n");
+    }
+}
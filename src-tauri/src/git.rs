@@ -0,0 +1,319 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tauri::Manager;
+
+use crate::frontmatter::{is_note_readonly, readonly_error};
+use crate::fs::{write_atomic, AppState};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Commit {
+    pub hash: String,
+    pub author: String,
+    pub date: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileStatus {
+    pub path: String,
+    pub status: String,
+}
+
+// The vault lock is per-machine state and must never end up in history.
+const GIT_EXCLUDE_LOCK: &str = ":(exclude).tau_lock";
+
+// Runs git inside the vault and returns stdout. Failures carry git's own
+// stderr so the user sees why.
+pub(crate) fn run_git(base_path: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(base_path)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", args.first().unwrap_or(&""), stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub(crate) fn ensure_git_repo(base_path: &Path) -> Result<(), String> {
+    match run_git(base_path, &["rev-parse", "--is-inside-work-tree"]) {
+        Ok(output) if output.trim() == "true" => Ok(()),
+        _ => Err("Vault is not a git repository".to_string()),
+    }
+}
+
+// Git wants forward slashes in pathspecs and revision paths on every
+// platform.
+fn git_path(rel: &str) -> String {
+    rel.replace('\\', "/")
+}
+
+#[tauri::command]
+pub async fn note_history(app_handle: tauri::AppHandle, rel: String, limit: usize) -> Result<Vec<Commit>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    file_history(base_path, &rel, limit)
+}
+
+fn file_history(base_path: &Path, rel: &str, limit: usize) -> Result<Vec<Commit>, String> {
+    ensure_git_repo(base_path)?;
+
+    // Fields are separated by \x1f and commits by \x1e, neither of which
+    // shows up in ordinary commit messages
+    let limit_arg = format!("--max-count={}", limit);
+    let output = run_git(
+        base_path,
+        &["log", &limit_arg, "--follow", "--format=%H%x1f%an%x1f%aI%x1f%B%x1e", "--", &git_path(rel)],
+    )?;
+
+    let mut commits = Vec::new();
+
+    for record in output.split('\x1e') {
+        let fields: Vec<&str> = record.trim_start_matches('\n').splitn(4, '\x1f').collect();
+        if fields.len() != 4 {
+            continue;
+        }
+
+        let date = chrono::DateTime::parse_from_rfc3339(fields[2])
+            .map(|date| date.to_rfc3339())
+            .map_err(|e| format!("Failed to parse commit date: {}", e))?;
+
+        commits.push(Commit {
+            hash: fields[0].to_string(),
+            author: fields[1].to_string(),
+            date,
+            message: fields[3].trim().to_string(),
+        });
+    }
+
+    Ok(commits)
+}
+
+// Writes the note's content at `commit` back to the working tree and returns
+// it. Nothing is staged or committed.
+#[tauri::command]
+pub async fn restore_note_revision(app_handle: tauri::AppHandle, rel: String, commit: String) -> Result<String, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    restore_revision(base_path, &rel, &commit)
+}
+
+fn restore_revision(base_path: &Path, rel: &str, commit: &str) -> Result<String, String> {
+    ensure_git_repo(base_path)?;
+
+    // Rejects option-like input as well as unknown revisions
+    let commit_spec = format!("{}^{{commit}}", commit);
+    if commit.starts_with('-') || run_git(base_path, &["rev-parse", "--verify", "--quiet", &commit_spec]).is_err() {
+        return Err(format!("Commit '{}' does not exist", commit));
+    }
+
+    let object = format!("{}:{}", commit, git_path(rel));
+    if run_git(base_path, &["cat-file", "-e", &object]).is_err() {
+        return Err(format!("'{}' is not tracked at commit '{}'", rel, commit));
+    }
+
+    let content = run_git(base_path, &["show", &object])?;
+
+    let file_path = base_path.join(rel);
+
+    if is_note_readonly(&file_path) {
+        return Err(readonly_error(rel));
+    }
+
+    // The note may have been deleted or moved since that revision
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directories: {}", e))?;
+    }
+
+    write_atomic(&file_path, content.as_bytes())
+        .map_err(|e| format!("Failed to write note: {}", e))?;
+
+    Ok(content)
+}
+
+// Maps a porcelain XY code to a single user-facing status.
+fn describe_status(code: &str) -> &'static str {
+    let mut chars = code.chars();
+    let (index, worktree) = (chars.next().unwrap_or(' '), chars.next().unwrap_or(' '));
+
+    match (index, worktree) {
+        ('?', '?') => "untracked",
+        ('U', _) | (_, 'U') | ('A', 'A') | ('D', 'D') => "conflicted",
+        ('R', _) => "renamed",
+        ('A', _) => "added",
+        ('D', _) | (_, 'D') => "deleted",
+        _ => "modified",
+    }
+}
+
+#[tauri::command]
+pub async fn git_status(app_handle: tauri::AppHandle) -> Result<Vec<FileStatus>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    ensure_git_repo(base_path)?;
+
+    let output = run_git(base_path, &["status", "--porcelain=v1", "-z", "--untracked-files=all", "--", ".", GIT_EXCLUDE_LOCK])?;
+
+    let mut statuses = Vec::new();
+    let mut records = output.split('\0');
+
+    while let Some(record) = records.next() {
+        if record.len() < 4 {
+            continue;
+        }
+
+        let (code, path) = record.split_at(3);
+        let code = code.trim_end();
+
+        // Renames are followed by a second record holding the old path
+        if code.starts_with('R') || code.starts_with('C') {
+            records.next();
+        }
+
+        statuses.push(FileStatus {
+            path: path.to_string(),
+            status: describe_status(code).to_string(),
+        });
+    }
+
+    Ok(statuses)
+}
+
+// Stages everything in the vault and commits it. Returns the new hash.
+#[tauri::command]
+pub async fn git_commit(app_handle: tauri::AppHandle, message: String) -> Result<String, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    commit_all(base_path, &message)
+}
+
+fn commit_all(base_path: &Path, message: &str) -> Result<String, String> {
+    if message.trim().is_empty() {
+        return Err("Commit message cannot be empty".to_string());
+    }
+
+    ensure_git_repo(base_path)?;
+
+    run_git(base_path, &["add", "--all", "--", ".", GIT_EXCLUDE_LOCK])?;
+
+    // `diff --quiet` exits non-zero exactly when something is staged
+    if run_git(base_path, &["diff", "--cached", "--quiet"]).is_ok() {
+        return Err("Nothing to commit".to_string());
+    }
+
+    run_git(base_path, &["commit", "--quiet", "-m", message])?;
+
+    Ok(run_git(base_path, &["rev-parse", "HEAD"])?.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    // A vault that is also a git repo, with a local identity so commits work
+    // on machines without a global git config.
+    fn git_vault() -> TempVault {
+        let vault = TempVault::new();
+        run_git(vault.path(), &["init", "--quiet"]).unwrap();
+        run_git(vault.path(), &["config", "user.name", "Test"]).unwrap();
+        run_git(vault.path(), &["config", "user.email", "test@example.com"]).unwrap();
+        run_git(vault.path(), &["config", "commit.gpgsign", "false"]).unwrap();
+        vault
+    }
+
+    fn commit(vault: &TempVault, message: &str) {
+        run_git(vault.path(), &["add", "--all"]).unwrap();
+        run_git(vault.path(), &["commit", "--quiet", "-m", message]).unwrap();
+    }
+
+    #[test]
+    fn history_lists_both_commits_newest_first() {
+        let vault = git_vault();
+        vault.write("Note.md", "first");
+        commit(&vault, "Add note");
+        vault.write("Note.md", "second");
+        vault.write("Other.md", "unrelated");
+        commit(&vault, "Edit note\n\nWith a body");
+
+        let history = file_history(vault.path(), "Note.md", 10).unwrap();
+
+        let messages: Vec<&str> = history.iter().map(|commit| commit.message.as_str()).collect();
+        assert_eq!(messages, vec!["Edit note\n\nWith a body", "Add note"]);
+        assert_eq!(history[0].author, "Test");
+        assert!(chrono::DateTime::parse_from_rfc3339(&history[0].date).is_ok());
+        assert_eq!(file_history(vault.path(), "Note.md", 1).unwrap().len(), 1);
+
+        let plain = TempVault::new();
+        assert_eq!(file_history(plain.path(), "Note.md", 10).unwrap_err(), "Vault is not a git repository");
+    }
+
+    #[test]
+    fn restoring_the_first_commit_rewrites_without_committing() {
+        let vault = git_vault();
+        vault.write("Note.md", "first");
+        commit(&vault, "Add note");
+        let first = run_git(vault.path(), &["rev-parse", "HEAD"]).unwrap().trim().to_string();
+        vault.write("Note.md", "second");
+        commit(&vault, "Edit note");
+
+        assert_eq!(restore_revision(vault.path(), "Note.md", &first).unwrap(), "first");
+        assert_eq!(vault.read("Note.md"), "first");
+        assert_eq!(run_git(vault.path(), &["rev-list", "--count", "HEAD"]).unwrap().trim(), "2");
+
+        assert!(restore_revision(vault.path(), "Note.md", "0000000").is_err());
+        assert!(restore_revision(vault.path(), "Note.md", "--all").is_err());
+        assert!(restore_revision(vault.path(), "Untracked.md", &first).is_err());
+    }
+
+    #[test]
+    fn committing_a_modified_note_returns_the_new_head() {
+        let vault = git_vault();
+        vault.write("Note.md", "first");
+        commit(&vault, "Add note");
+        vault.write("Note.md", "second");
+        vault.write(".tau_lock", "held");
+
+        let hash = commit_all(vault.path(), "Save").unwrap();
+
+        assert_eq!(hash, run_git(vault.path(), &["rev-parse", "HEAD"]).unwrap().trim());
+        assert_eq!(run_git(vault.path(), &["show", "HEAD:Note.md"]).unwrap(), "second");
+        // The lock stays out of history
+        assert!(run_git(vault.path(), &["cat-file", "-e", "HEAD:.tau_lock"]).is_err());
+
+        assert_eq!(commit_all(vault.path(), "Again").unwrap_err(), "Nothing to commit");
+        assert!(commit_all(vault.path(), "  ").is_err());
+    }
+}
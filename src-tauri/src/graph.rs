@@ -0,0 +1,763 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::Path;
+use tauri::Manager;
+
+use crate::fs::{calculate_similarity, with_note_content, AppState};
+use crate::links::{collect_vault_links, note_stem, NoteIndex};
+use crate::tags::extract_tags;
+use crate::timing::timed;
+
+// Upper bound on nodes returned for a neighborhood so hub notes can't
+// expand to the entire vault.
+const MAX_NEIGHBORHOOD_NODES: usize = 200;
+
+// Upper bound on notes visited while looking for a path between two notes.
+const MAX_PATH_SEARCH_NODES: usize = 10_000;
+
+// Caps for `longest_paths`: no chain grows past MAX_CHAIN_LENGTH notes, and
+// the whole search stops after MAX_CHAIN_SEARCH_STEPS extensions so densely
+// linked vaults can't explode combinatorially.
+const MAX_CHAIN_LENGTH: usize = 50;
+const MAX_CHAIN_SEARCH_STEPS: usize = 100_000;
+
+// Weights for `related_notes`. A shared tag is the strongest signal, then a
+// shared link target or a direct link; title similarity only breaks ties
+// between otherwise similar notes.
+const SHARED_TAG_WEIGHT: f64 = 2.0;
+const SHARED_LINK_WEIGHT: f64 = 1.5;
+const DIRECT_LINK_WEIGHT: f64 = 1.0;
+const TITLE_SIMILARITY_THRESHOLD: f64 = 0.5;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GraphNode {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GraphEdge {
+    pub source: String,
+    pub target: String,
+    // Number of links from source to target
+    pub weight: usize,
+    // Whether target also links back to source
+    pub bidirectional: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedNote {
+    pub path: String,
+    pub score: f64,
+    // Human-readable explanation, e.g. "2 shared tags"
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FolderDensity {
+    // Vault-relative folder, "" for the root
+    pub path: String,
+    pub notes: usize,
+    // Distinct note-to-note links within the folder
+    pub intra_links: usize,
+    // Distinct links between this folder and any other, in either direction
+    pub cross_links: usize,
+    // intra_links per note
+    pub density: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Graph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    pub truncated: bool,
+}
+
+// Resolved note-to-note links, one entry per distinct (source, target) pair.
+pub(crate) struct LinkGraph {
+    pub(crate) notes: Vec<String>,
+    pub(crate) outgoing: BTreeMap<String, BTreeSet<String>>,
+    pub(crate) incoming: BTreeMap<String, BTreeSet<String>>,
+    pub(crate) weights: HashMap<(String, String), usize>,
+}
+
+impl LinkGraph {
+    pub(crate) fn build(base_path: &Path) -> Result<Self, String> {
+        let index = NoteIndex::build(base_path)?;
+        let mut outgoing: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut incoming: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut weights: HashMap<(String, String), usize> = HashMap::new();
+
+        for link in collect_vault_links(base_path, &index)? {
+            if let Some(target) = index.resolve_link_target(&link.source_file, &link.target_note) {
+                *weights.entry((link.source_file.clone(), target.clone())).or_insert(0) += 1;
+                outgoing.entry(link.source_file.clone()).or_default().insert(target.clone());
+                incoming.entry(target).or_default().insert(link.source_file);
+            }
+        }
+
+        Ok(LinkGraph { notes: index.notes, outgoing, incoming, weights })
+    }
+
+    pub(crate) fn neighbors(&self, note: &str) -> BTreeSet<&String> {
+        self.outgoing
+            .get(note)
+            .into_iter()
+            .chain(self.incoming.get(note))
+            .flatten()
+            .collect()
+    }
+
+    // Nodes and edges restricted to `included`, keeping the given node order.
+    fn subgraph(&self, included: &[String], truncated: bool) -> Graph {
+        let members: HashSet<&String> = included.iter().collect();
+
+        let nodes = included
+            .iter()
+            .map(|note| GraphNode { id: note.clone(), name: note_stem(note).to_string() })
+            .collect();
+
+        let edges = self
+            .outgoing
+            .iter()
+            .filter(|(source, _)| members.contains(source))
+            .flat_map(|(source, targets)| {
+                targets
+                    .iter()
+                    .filter(|target| members.contains(target))
+                    .map(move |target| GraphEdge {
+                        source: source.clone(),
+                        target: target.clone(),
+                        weight: self.weights.get(&(source.clone(), target.clone())).copied().unwrap_or(1),
+                        bidirectional: self.outgoing.get(target).is_some_and(|back| back.contains(source)),
+                    })
+            })
+            .collect();
+
+        Graph { nodes, edges, truncated }
+    }
+}
+
+fn neighborhood(graph: &LinkGraph, start: &str, depth: usize) -> Graph {
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut queue: VecDeque<(String, usize)> = VecDeque::new();
+    let mut truncated = false;
+
+    visited.insert(start.to_string());
+    order.push(start.to_string());
+    queue.push_back((start.to_string(), 0));
+
+    'outer: while let Some((note, distance)) = queue.pop_front() {
+        if distance == depth {
+            continue;
+        }
+
+        for neighbor in graph.neighbors(&note) {
+            if visited.contains(neighbor) {
+                continue;
+            }
+            if order.len() >= MAX_NEIGHBORHOOD_NODES {
+                truncated = true;
+                break 'outer;
+            }
+            visited.insert(neighbor.clone());
+            order.push(neighbor.clone());
+            queue.push_back((neighbor.clone(), distance + 1));
+        }
+    }
+
+    graph.subgraph(&order, truncated)
+}
+
+// Notes reachable from `start` by following outgoing links, in BFS order
+// and including `start` itself. `max_depth` of None means no limit.
+pub(crate) fn reachable(graph: &LinkGraph, start: &str, max_depth: Option<usize>) -> Vec<String> {
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut order: Vec<String> = Vec::new();
+    let mut queue: VecDeque<(String, usize)> = VecDeque::new();
+
+    visited.insert(start.to_string());
+    order.push(start.to_string());
+    queue.push_back((start.to_string(), 0));
+
+    while let Some((note, distance)) = queue.pop_front() {
+        if max_depth == Some(distance) {
+            continue;
+        }
+
+        for target in graph.outgoing.get(&note).into_iter().flatten() {
+            if visited.insert(target.clone()) {
+                order.push(target.clone());
+                queue.push_back((target.clone(), distance + 1));
+            }
+        }
+    }
+
+    order
+}
+
+// Rotates a cycle so it starts at its smallest path, giving each loop one
+// spelling no matter where the search entered it.
+fn canonical_cycle(cycle: &[String]) -> Vec<String> {
+    let start = cycle
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, note)| *note)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+
+    cycle[start..].iter().chain(&cycle[..start]).cloned().collect()
+}
+
+// Cycles closed by back edges of a depth-first search over outgoing links.
+// The search keeps an explicit stack so long link chains can't overflow the
+// thread stack. Self-links are ignored.
+fn link_cycles(graph: &LinkGraph) -> Vec<Vec<String>> {
+    let mut finished: HashSet<&String> = HashSet::new();
+    let mut cycles: BTreeSet<Vec<String>> = BTreeSet::new();
+
+    for root in &graph.notes {
+        if finished.contains(root) {
+            continue;
+        }
+
+        let mut path: Vec<&String> = vec![root];
+        let mut on_path: HashMap<&String, usize> = HashMap::from([(root, 0)]);
+        let mut pending = vec![graph.outgoing.get(root).into_iter().flatten()];
+
+        while let Some(targets) = pending.last_mut() {
+            let note = path[path.len() - 1];
+
+            match targets.next() {
+                Some(target) if target == note || finished.contains(target) => {}
+                Some(target) => match on_path.get(target) {
+                    Some(&start) => {
+                        let cycle: Vec<String> = path[start..].iter().map(|n| n.to_string()).collect();
+                        cycles.insert(canonical_cycle(&cycle));
+                    }
+                    None => {
+                        on_path.insert(target, path.len());
+                        path.push(target);
+                        pending.push(graph.outgoing.get(target).into_iter().flatten());
+                    }
+                },
+                None => {
+                    pending.pop();
+                    path.pop();
+                    on_path.remove(note);
+                    finished.insert(note);
+                }
+            }
+        }
+    }
+
+    cycles.into_iter().collect()
+}
+
+// Longest simple path of outgoing links starting at `root`, searched depth
+// first with an explicit stack like `link_cycles`. `steps` is shared across
+// roots so the budget covers the whole search.
+fn longest_chain_from<'a>(graph: &'a LinkGraph, root: &'a String, steps: &mut usize) -> Vec<&'a String> {
+    let mut path: Vec<&String> = vec![root];
+    let mut on_path: HashSet<&String> = HashSet::from([root]);
+    let mut pending = vec![graph.outgoing.get(root).into_iter().flatten()];
+    let mut best = path.clone();
+
+    while let Some(targets) = pending.last_mut() {
+        let note = path[path.len() - 1];
+
+        match targets.next() {
+            Some(target) if on_path.contains(target) => {}
+            Some(_) if path.len() >= MAX_CHAIN_LENGTH || *steps >= MAX_CHAIN_SEARCH_STEPS => {}
+            Some(target) => {
+                *steps += 1;
+                on_path.insert(target);
+                path.push(target);
+                pending.push(graph.outgoing.get(target).into_iter().flatten());
+                if path.len() > best.len() {
+                    best = path.clone();
+                }
+            }
+            None => {
+                pending.pop();
+                path.pop();
+                on_path.remove(note);
+            }
+        }
+    }
+
+    best
+}
+
+// The longest reading sequences in the vault, longest first. A chain that
+// runs along part of a longer one already listed is dropped, so each thread
+// shows up once.
+fn longest_chains(graph: &LinkGraph, limit: usize) -> Vec<Vec<String>> {
+    let mut steps = 0;
+    let mut candidates: Vec<Vec<&String>> = graph
+        .notes
+        .iter()
+        .map(|root| longest_chain_from(graph, root, &mut steps))
+        .filter(|chain| chain.len() > 1)
+        .collect();
+
+    candidates.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+    let mut chains: Vec<Vec<&String>> = Vec::new();
+    for candidate in candidates {
+        if chains.len() >= limit {
+            break;
+        }
+        let covered = chains
+            .iter()
+            .any(|chain| chain.windows(candidate.len()).any(|window| window == candidate.as_slice()));
+        if !covered {
+            chains.push(candidate);
+        }
+    }
+
+    chains
+        .into_iter()
+        .map(|chain| chain.into_iter().cloned().collect())
+        .collect()
+}
+
+// BFS from `from` to `to`, following outgoing links only when `directed`
+// and links in either direction otherwise. Returns the notes along the way,
+// both ends included, or None if unreachable within the search cap.
+fn shortest_path(graph: &LinkGraph, from: &str, to: &str, directed: bool) -> Option<Vec<String>> {
+    let mut previous: HashMap<String, String> = HashMap::new();
+    let mut visited: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<String> = VecDeque::new();
+
+    visited.insert(from.to_string());
+    queue.push_back(from.to_string());
+
+    while let Some(note) = queue.pop_front() {
+        if note == to {
+            let mut path = vec![note];
+            while let Some(prev) = previous.get(path.last()?) {
+                path.push(prev.clone());
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let next: BTreeSet<&String> = if directed {
+            graph.outgoing.get(&note).into_iter().flatten().collect()
+        } else {
+            graph.neighbors(&note)
+        };
+
+        for neighbor in next {
+            if visited.len() >= MAX_PATH_SEARCH_NODES {
+                return None;
+            }
+            if visited.insert(neighbor.clone()) {
+                previous.insert(neighbor.clone(), note.clone());
+                queue.push_back(neighbor.clone());
+            }
+        }
+    }
+
+    None
+}
+
+#[tauri::command]
+pub async fn get_graph(app_handle: tauri::AppHandle) -> Result<Graph, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    timed!("get_graph", base_path.display(), {
+        let graph = LinkGraph::build(base_path)?;
+        Ok(graph.subgraph(&graph.notes, false))
+    })
+}
+
+#[tauri::command]
+pub async fn note_neighborhood(app_handle: tauri::AppHandle, rel: String, depth: usize) -> Result<Graph, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let graph = LinkGraph::build(base_path)?;
+
+    if !graph.notes.contains(&rel) {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    Ok(neighborhood(&graph, &rel, depth))
+}
+
+#[tauri::command]
+pub async fn reachable_notes(app_handle: tauri::AppHandle, rel: String, max_depth: Option<usize>) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let graph = LinkGraph::build(base_path)?;
+
+    if !graph.notes.contains(&rel) {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    Ok(reachable(&graph, &rel, max_depth))
+}
+
+#[tauri::command]
+pub async fn find_cycles(app_handle: tauri::AppHandle) -> Result<Vec<Vec<String>>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let graph = LinkGraph::build(base_path)?;
+    Ok(link_cycles(&graph))
+}
+
+#[tauri::command]
+pub async fn shortest_link_path(app_handle: tauri::AppHandle, from: String, to: String, directed: Option<bool>) -> Result<Option<Vec<String>>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let graph = LinkGraph::build(base_path)?;
+
+    for rel in [&from, &to] {
+        if !graph.notes.contains(rel) {
+            return Err(format!("File '{}' does not exist", rel));
+        }
+    }
+
+    Ok(shortest_path(&graph, &from, &to, directed.unwrap_or(false)))
+}
+
+#[tauri::command]
+pub async fn longest_paths(app_handle: tauri::AppHandle, limit: usize) -> Result<Vec<Vec<String>>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let graph = LinkGraph::build(base_path)?;
+    Ok(longest_chains(&graph, limit))
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
+
+// Other notes ranked by shared tags, shared link targets, links between
+// the two and title similarity. Notes with nothing in common are left out.
+#[tauri::command]
+pub async fn related_notes(app_handle: tauri::AppHandle, rel: String, limit: usize) -> Result<Vec<RelatedNote>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let graph = LinkGraph::build(base_path)?;
+
+    if !graph.notes.contains(&rel) {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    rank_related(base_path, &graph, &rel, limit)
+}
+
+fn rank_related(base_path: &Path, graph: &LinkGraph, rel: &str, limit: usize) -> Result<Vec<RelatedNote>, String> {
+    let no_links = BTreeSet::new();
+    let own_links = graph.outgoing.get(rel).unwrap_or(&no_links);
+    let own_tags = with_note_content(&base_path.join(rel), extract_tags)?;
+
+    let mut related = Vec::new();
+
+    for note in &graph.notes {
+        if note == rel {
+            continue;
+        }
+
+        let mut score = 0.0;
+        let mut reasons = Vec::new();
+
+        let tags = with_note_content(&base_path.join(note), extract_tags)?;
+        let shared_tags = own_tags.intersection(&tags).count();
+        if shared_tags > 0 {
+            score += shared_tags as f64 * SHARED_TAG_WEIGHT;
+            reasons.push(plural(shared_tags, "shared tag", "shared tags"));
+        }
+
+        let links = graph.outgoing.get(note).unwrap_or(&no_links);
+        let shared_links = own_links.intersection(links).filter(|target| *target != rel && *target != note).count();
+        if shared_links > 0 {
+            score += shared_links as f64 * SHARED_LINK_WEIGHT;
+            reasons.push(plural(shared_links, "shared link", "shared links"));
+        }
+
+        if own_links.contains(note) {
+            score += DIRECT_LINK_WEIGHT;
+            reasons.push("Linked from this note".to_string());
+        }
+        if links.contains(rel) {
+            score += DIRECT_LINK_WEIGHT;
+            reasons.push("Links to this note".to_string());
+        }
+
+        let similarity = calculate_similarity(note_stem(rel), note_stem(note));
+        if similarity >= TITLE_SIMILARITY_THRESHOLD {
+            score += similarity;
+            reasons.push("Similar title".to_string());
+        }
+
+        if score > 0.0 {
+            related.push(RelatedNote { path: note.clone(), score, reasons });
+        }
+    }
+
+    related.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    related.truncate(limit);
+
+    Ok(related)
+}
+
+fn note_folder(rel: &str) -> &str {
+    rel.rsplit_once('/').map(|(folder, _)| folder).unwrap_or("")
+}
+
+// Per-folder link structure: links that stay inside each folder versus
+// links crossing into or out of it. Only a note's own folder counts, so
+// a link from `a/b/x.md` to `a/y.md` is cross-folder for both `a/b` and `a`.
+#[tauri::command]
+pub async fn folder_link_density(app_handle: tauri::AppHandle) -> Result<Vec<FolderDensity>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let graph = LinkGraph::build(base_path)?;
+    Ok(link_density(&graph))
+}
+
+fn link_density(graph: &LinkGraph) -> Vec<FolderDensity> {
+    let mut folders: BTreeMap<&str, FolderDensity> = BTreeMap::new();
+
+    for note in &graph.notes {
+        let folder = note_folder(note);
+        folders
+            .entry(folder)
+            .or_insert_with(|| FolderDensity { path: folder.to_string(), notes: 0, intra_links: 0, cross_links: 0, density: 0.0 })
+            .notes += 1;
+    }
+
+    for (source, targets) in &graph.outgoing {
+        let source_folder = note_folder(source);
+        for target in targets.iter().filter(|target| *target != source) {
+            let target_folder = note_folder(target);
+            if source_folder == target_folder {
+                if let Some(folder) = folders.get_mut(source_folder) {
+                    folder.intra_links += 1;
+                }
+            } else {
+                for side in [source_folder, target_folder] {
+                    if let Some(folder) = folders.get_mut(side) {
+                        folder.cross_links += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    folders
+        .into_values()
+        .map(|mut folder| {
+            folder.density = folder.intra_links as f64 / folder.notes as f64;
+            folder
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    // Builds the link graph of a vault holding `notes` as (path, content)
+    fn graph_of(notes: &[(&str, &str)]) -> LinkGraph {
+        let vault = TempVault::new();
+        for (path, content) in notes {
+            vault.write(path, content);
+        }
+        LinkGraph::build(vault.path()).unwrap()
+    }
+
+    fn node_ids(graph: &Graph) -> Vec<&str> {
+        graph.nodes.iter().map(|node| node.id.as_str()).collect()
+    }
+
+    #[test]
+    fn neighborhood_grows_one_hop_per_depth_in_both_directions() {
+        let graph = graph_of(&[("A.md", "[[B]]"), ("B.md", "[[C]]"), ("C.md", "[[D]]"), ("D.md", ""), ("E.md", "")]);
+
+        let near = neighborhood(&graph, "B.md", 1);
+        assert_eq!(node_ids(&near), vec!["B.md", "A.md", "C.md"]);
+        assert_eq!(near.edges.len(), 2);
+        assert!(!near.truncated);
+
+        let wider = neighborhood(&graph, "B.md", 2);
+        assert_eq!(node_ids(&wider), vec!["B.md", "A.md", "C.md", "D.md"]);
+        assert_eq!(wider.edges.len(), 3);
+
+        assert_eq!(node_ids(&neighborhood(&graph, "B.md", 0)), vec!["B.md"]);
+    }
+
+    #[test]
+    fn reachable_terminates_on_a_cycle() {
+        let graph = graph_of(&[("A.md", "[[B]] [[Missing]]"), ("B.md", "[[C]]"), ("C.md", "[[A]] [[D]]"), ("D.md", ""), ("E.md", "[[A]]")]);
+
+        assert_eq!(reachable(&graph, "A.md", None), vec!["A.md", "B.md", "C.md", "D.md"]);
+        assert_eq!(reachable(&graph, "A.md", Some(1)), vec!["A.md", "B.md"]);
+        assert_eq!(reachable(&graph, "D.md", None), vec!["D.md"]);
+    }
+
+    #[test]
+    fn three_note_cycle_is_reported_once_and_a_dag_has_none() {
+        let cyclic = graph_of(&[("B.md", "[[C]]"), ("C.md", "[[A]]"), ("A.md", "[[B]] [[A]]"), ("D.md", "[[A]]")]);
+        assert_eq!(link_cycles(&cyclic), vec![vec!["A.md".to_string(), "B.md".to_string(), "C.md".to_string()]]);
+
+        let dag = graph_of(&[("A.md", "[[B]] [[C]]"), ("B.md", "[[D]]"), ("C.md", "[[D]]"), ("D.md", "")]);
+        assert!(link_cycles(&dag).is_empty());
+    }
+
+    #[test]
+    fn edge_weights_count_links_per_direction() {
+        let graph = graph_of(&[("A.md", "[[B]] [[B|again]] [[B#Part]]"), ("B.md", "[[A]]"), ("C.md", "[[A]]")]);
+        let full = graph.subgraph(&graph.notes, false);
+
+        let mut edges: Vec<(&str, &str, usize, bool)> = full
+            .edges
+            .iter()
+            .map(|edge| (edge.source.as_str(), edge.target.as_str(), edge.weight, edge.bidirectional))
+            .collect();
+        edges.sort();
+
+        assert_eq!(edges, vec![
+            ("A.md", "B.md", 3, true),
+            ("B.md", "A.md", 1, true),
+            ("C.md", "A.md", 1, false),
+        ]);
+    }
+
+    #[test]
+    fn shortest_path_finds_reachable_pairs_only() {
+        let graph = graph_of(&[
+            ("A.md", "[[B]] [[Long1]]"),
+            ("B.md", "[[C]]"),
+            ("Long1.md", "[[Long2]]"),
+            ("Long2.md", "[[C]]"),
+            ("C.md", ""),
+            ("Island.md", "[[Other]]"),
+            ("Other.md", ""),
+        ]);
+        let path = |notes: &[&str]| Some(notes.iter().map(|note| note.to_string()).collect::<Vec<_>>());
+
+        assert_eq!(shortest_path(&graph, "A.md", "C.md", true), path(&["A.md", "B.md", "C.md"]));
+        // Walking against the link direction needs the undirected search
+        assert_eq!(shortest_path(&graph, "C.md", "A.md", true), None);
+        assert_eq!(shortest_path(&graph, "C.md", "A.md", false), path(&["C.md", "B.md", "A.md"]));
+
+        assert_eq!(shortest_path(&graph, "A.md", "Island.md", false), None);
+        assert_eq!(shortest_path(&graph, "A.md", "A.md", true), path(&["A.md"]));
+    }
+
+    #[test]
+    fn shared_tags_and_links_outrank_a_shared_tag() {
+        let vault = TempVault::new();
+        vault.write("Plan.md", "#launch [[Hub]]");
+        vault.write("Budget.md", "#launch [[Hub]]");
+        vault.write("Roster.md", "#launch");
+        vault.write("Hub.md", "");
+        vault.write("Unrelated.md", "#other");
+
+        let graph = LinkGraph::build(vault.path()).unwrap();
+        let related = rank_related(vault.path(), &graph, "Plan.md", 10).unwrap();
+        let ranked: Vec<&str> = related.iter().map(|note| note.path.as_str()).collect();
+
+        assert_eq!(ranked, vec!["Budget.md", "Roster.md", "Hub.md"]);
+        assert_eq!(related[0].reasons, vec!["1 shared tag", "1 shared link"]);
+        assert_eq!(related[1].reasons, vec!["1 shared tag"]);
+        assert_eq!(rank_related(vault.path(), &graph, "Plan.md", 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn density_separates_intra_and_cross_folder_links() {
+        let graph = graph_of(&[
+            ("Work/a.md", "[[b]] [[b|again]] [[Home/c]]"),
+            ("Work/b.md", "[[a]]"),
+            ("Home/c.md", "[[d]]"),
+            ("Home/d.md", "[[Work/a]] [[d]]"),
+        ]);
+
+        let folders = link_density(&graph);
+        let density: Vec<(&str, usize, usize, usize)> = folders
+            .iter()
+            .map(|folder| (folder.path.as_str(), folder.notes, folder.intra_links, folder.cross_links))
+            .collect();
+
+        // Self-links don't count and repeated links count once
+        assert_eq!(density, vec![("Home", 2, 1, 2), ("Work", 2, 2, 2)]);
+        assert_eq!(folders[1].density, 1.0);
+    }
+
+    #[test]
+    fn four_note_chain_is_the_longest_path() {
+        let graph = graph_of(&[
+            ("Ch1.md", "[[Ch2]]"),
+            ("Ch2.md", "[[Ch3]] [[Ch1]]"),
+            ("Ch3.md", "[[Ch4]]"),
+            ("Ch4.md", ""),
+            ("Aside.md", "[[Ch3]]"),
+        ]);
+
+        // Chains running along the top one, like Ch2 -> Ch4, aren't listed again
+        assert_eq!(longest_chains(&graph, 5), vec![
+            vec!["Ch1.md", "Ch2.md", "Ch3.md", "Ch4.md"],
+            vec!["Aside.md", "Ch3.md", "Ch4.md"],
+        ]);
+        assert_eq!(longest_chains(&graph, 1).len(), 1);
+    }
+}
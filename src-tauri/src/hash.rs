@@ -0,0 +1,95 @@
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::Path;
+use tauri::Manager;
+
+use crate::fs::{collect_markdown_files, relative_path_string, AppState};
+
+// Lowercase hex SHA-256 of a file's bytes, streamed so large notes aren't
+// loaded into memory at once.
+fn hash_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut hasher = Sha256::new();
+
+    io::copy(&mut file, &mut hasher)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn note_hash(app_handle: tauri::AppHandle, rel: String) -> Result<String, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let file_path = base_path.join(&rel);
+
+    if !file_path.is_file() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    hash_file(&file_path)
+}
+
+pub(crate) fn note_hashes(base_path: &Path) -> Result<BTreeMap<String, String>, String> {
+    let mut hashes = BTreeMap::new();
+
+    for path in collect_markdown_files(base_path)? {
+        hashes.insert(relative_path_string(base_path, &path)?, hash_file(&path)?);
+    }
+
+    Ok(hashes)
+}
+
+// Content hashes of every note, keyed by vault-relative path, for sync tools
+// that can't rely on mtimes.
+#[tauri::command]
+pub async fn vault_hashes(app_handle: tauri::AppHandle) -> Result<HashMap<String, String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    Ok(note_hashes(base_path)?.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn identical_bytes_hash_alike_and_one_byte_differs() {
+        let vault = TempVault::new();
+        let first = vault.write("First.md", "same content\n");
+        let copy = vault.write("Sub/Copy.md", "same content\n");
+        let changed = vault.write("Changed.md", "same content!");
+
+        assert_eq!(hash_file(&first).unwrap(), hash_file(&copy).unwrap());
+        assert_ne!(hash_file(&first).unwrap(), hash_file(&changed).unwrap());
+        // SHA-256 of the empty input
+        assert_eq!(
+            hash_file(&vault.write("Empty.md", "")).unwrap(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        let hashes = note_hashes(vault.path()).unwrap();
+        assert_eq!(hashes["First.md"], hashes["Sub/Copy.md"]);
+        assert_eq!(hashes.len(), 4);
+    }
+}
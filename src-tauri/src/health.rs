@@ -0,0 +1,94 @@
+use std::path::Path;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+use crate::fs::AppState;
+use crate::watch;
+
+pub const VAULT_UNAVAILABLE_EVENT: &str = "vault://unavailable";
+pub const VAULT_AVAILABLE_EVENT: &str = "vault://available";
+
+// How often the background monitor looks for the vault root.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+// Whether the vault root exists now, and the event to emit if that changed
+// since the last check.
+fn health_change(vault_path: &Path, was_missing: bool) -> (bool, Option<&'static str>) {
+    let available = vault_path.is_dir();
+
+    let event = match (available, was_missing) {
+        (false, false) => Some(VAULT_UNAVAILABLE_EVENT),
+        (true, true) => Some(VAULT_AVAILABLE_EVENT),
+        _ => None,
+    };
+
+    (available, event)
+}
+
+// Whether the vault root still exists, emitting VAULT_UNAVAILABLE_EVENT when
+// it disappears (an unmounted drive or network share) and
+// VAULT_AVAILABLE_EVENT when it comes back. Both carry the vault path.
+pub(crate) fn check_vault_health(app_handle: &tauri::AppHandle) -> Result<bool, String> {
+    let (vault_path, event, available) = {
+        let state = app_handle.state::<std::sync::Mutex<AppState>>();
+        let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+
+        let vault_path = match &state_guard.vault_path {
+            Some(vault_path) => vault_path.clone(),
+            None => return Err("No vault set".to_string()),
+        };
+
+        let (available, event) = health_change(&vault_path, state_guard.vault_missing);
+        state_guard.vault_missing = !available;
+        (vault_path, event, available)
+    };
+
+    if let Some(event) = event {
+        if event == VAULT_AVAILABLE_EVENT {
+            watch::rewatch(app_handle, &vault_path)?;
+        }
+        app_handle
+            .emit(event, vault_path.to_string_lossy().to_string())
+            .map_err(|e| format!("Failed to emit event: {}", e))?;
+    }
+
+    Ok(available)
+}
+
+// Polls the vault root for the lifetime of the app. Polling rather than a
+// watcher, since a watch on a vanished mount point can't report it coming
+// back.
+pub(crate) fn spawn_health_monitor(app_handle: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(HEALTH_CHECK_INTERVAL);
+        // No vault set yet is not worth reporting
+        let _ = check_vault_health(&app_handle);
+    });
+}
+
+#[tauri::command]
+pub async fn vault_available(app_handle: tauri::AppHandle) -> Result<bool, String> {
+    check_vault_health(&app_handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn removing_the_vault_is_reported_once_until_it_returns() {
+        let vault = TempVault::new();
+        let root = vault.path().join("Mounted");
+        std::fs::create_dir(&root).unwrap();
+
+        assert_eq!(health_change(&root, false), (true, None));
+
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(health_change(&root, false), (false, Some(VAULT_UNAVAILABLE_EVENT)));
+        assert_eq!(health_change(&root, true), (false, None));
+
+        std::fs::create_dir(&root).unwrap();
+        assert_eq!(health_change(&root, true), (true, Some(VAULT_AVAILABLE_EVENT)));
+    }
+}
@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::frontmatter::{is_note_readonly, parse_frontmatter, readonly_error, split_frontmatter, with_frontmatter, Frontmatter};
+use crate::fs::{write_atomic, AppState};
+
+// Stable note ids mapped to each note's current vault-relative path.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct IdMap {
+    pub ids: BTreeMap<String, String>,
+}
+
+fn get_id_map_path(base_path: &Path) -> PathBuf {
+    base_path.join(".tau_ids.json")
+}
+
+pub(crate) fn read_id_map(base_path: &Path) -> IdMap {
+    fs::read_to_string(get_id_map_path(base_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_id_map(base_path: &Path, id_map: &IdMap) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(id_map)
+        .map_err(|e| format!("Failed to serialize id map: {}", e))?;
+
+    write_atomic(&get_id_map_path(base_path), content.as_bytes())
+        .map_err(|e| format!("Failed to write id map: {}", e))
+}
+
+// Map keys always use forward slashes so they compare equal across platforms.
+fn id_map_key(rel: &str) -> String {
+    rel.replace('\\', "/")
+}
+
+// Rewrites `path` if it is `from` or lies inside the folder `from`.
+fn moved_path(path: &str, from: &str, to: &str) -> Option<String> {
+    if path == from {
+        return Some(to.to_string());
+    }
+    path.strip_prefix(from)
+        .filter(|rest| rest.starts_with('/'))
+        .map(|rest| format!("{}{}", to, rest))
+}
+
+// Keeps ids pointing at a note (or every note in a folder) that moved from
+// `from` to `to`. Vaults without an id map are left alone.
+pub(crate) fn record_move(base_path: &Path, from: &str, to: &str) -> Result<(), String> {
+    if !get_id_map_path(base_path).exists() {
+        return Ok(());
+    }
+
+    let (from, to) = (id_map_key(from), id_map_key(to));
+    let mut id_map = read_id_map(base_path);
+    let mut changed = false;
+
+    for path in id_map.ids.values_mut() {
+        if let Some(new_path) = moved_path(path, &from, &to) {
+            *path = new_path;
+            changed = true;
+        }
+    }
+
+    if changed {
+        write_id_map(base_path, &id_map)?;
+    }
+    Ok(())
+}
+
+// Drops ids of a deleted note, or of every note in a deleted folder.
+pub(crate) fn record_removal(base_path: &Path, rel: &str) -> Result<(), String> {
+    if !get_id_map_path(base_path).exists() {
+        return Ok(());
+    }
+
+    let rel = id_map_key(rel);
+    let mut id_map = read_id_map(base_path);
+    let before = id_map.ids.len();
+
+    id_map.ids.retain(|_, path| moved_path(path, &rel, "").is_none());
+
+    if id_map.ids.len() != before {
+        write_id_map(base_path, &id_map)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn frontmatter_id(content: &str) -> Option<String> {
+    parse_frontmatter(content)?
+        .get("id")?
+        .as_str()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+}
+
+// Returns the note's id, writing a new UUID into its frontmatter first if it
+// has none. Either way the id map is brought up to date.
+#[tauri::command]
+pub async fn assign_note_id(app_handle: tauri::AppHandle, rel: String) -> Result<String, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let file_path = base_path.join(&rel);
+
+    if !file_path.is_file() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read note: {}", e))?;
+
+    let id = match frontmatter_id(&content) {
+        Some(id) => id,
+        None => {
+            if is_note_readonly(&file_path) {
+                return Err(readonly_error(&rel));
+            }
+
+            let mut frontmatter = match parse_frontmatter(&content) {
+                Some(frontmatter) => frontmatter,
+                None if split_frontmatter(&content).is_some() => return Err("Frontmatter is not valid YAML".to_string()),
+                None => Frontmatter::new(),
+            };
+
+            let id = uuid::Uuid::new_v4().to_string();
+            frontmatter.insert("id".into(), id.clone().into());
+
+            write_atomic(&file_path, with_frontmatter(&content, &frontmatter)?.as_bytes())
+                .map_err(|e| format!("Failed to write note: {}", e))?;
+            id
+        }
+    };
+
+    let mut id_map = read_id_map(base_path);
+    let key = id_map_key(&rel);
+    if id_map.ids.get(&id) != Some(&key) {
+        id_map.ids.insert(id.clone(), key);
+        write_id_map(base_path, &id_map)?;
+    }
+
+    Ok(id)
+}
+
+// None for ids that were never assigned or whose note no longer exists.
+#[tauri::command]
+pub async fn resolve_id(app_handle: tauri::AppHandle, id: String) -> Result<Option<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    Ok(read_id_map(base_path)
+        .ids
+        .remove(id.trim())
+        .filter(|path| base_path.join(path).is_file()))
+}
@@ -0,0 +1,344 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use tauri::Manager;
+
+use crate::attachments::FileIndex;
+use crate::frontmatter::{parse_frontmatter, split_frontmatter, Frontmatter};
+use crate::fs::{collect_markdown_files, parse_links_from_content, relative_path_string, with_note_content, AppState};
+use crate::ordering;
+use crate::links::{note_stem, NoteIndex};
+
+// Each section lists the offending vault-relative paths, sorted.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct IntegrityReport {
+    // Notes containing at least one link that resolves to nothing
+    pub broken_links: Vec<String>,
+    // Notes sharing a file name with another note
+    pub duplicate_titles: Vec<String>,
+    // Notes with no links in or out
+    pub orphan_notes: Vec<String>,
+    pub malformed_frontmatter: Vec<String>,
+    // `folder/name` for order-file entries that no longer exist
+    pub stale_order_entries: Vec<String>,
+    pub unused_attachments: Vec<String>,
+    // Notes that aren't valid UTF-8; their other checks run on the lossily
+    // decoded text
+    pub encoding_problems: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FrontmatterProblem {
+    pub path: String,
+    pub reason: String,
+}
+
+// Why a note's frontmatter is broken: it opens a `---` block that never
+// closes, or the block isn't a YAML mapping. Notes without frontmatter are
+// fine and give None.
+fn frontmatter_problem(content: &str) -> Option<String> {
+    let opens_block = content.starts_with("---\n") || content.starts_with("---\r\n");
+    if !opens_block {
+        return None;
+    }
+
+    let (yaml, _) = match split_frontmatter(content) {
+        Some(block) => block,
+        None => return Some("Frontmatter block is never closed".to_string()),
+    };
+
+    if parse_frontmatter(content).is_some() {
+        return None;
+    }
+
+    match serde_yaml::from_str::<Frontmatter>(yaml) {
+        Err(e) => Some(format!("Invalid YAML: {}", e)),
+        Ok(_) => Some("Frontmatter is not a key/value mapping".to_string()),
+    }
+}
+
+fn collect_stale_order_entries(base_path: &Path, dir: &Path, stale: &mut Vec<String>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory: {}", e))?;
+
+    let mut names = HashSet::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if entry.path().is_dir() && !name.starts_with('.') {
+            collect_stale_order_entries(base_path, &entry.path(), stale)?;
+        }
+        names.insert(name);
+    }
+
+    for name in ordering::load(dir).names() {
+        if !names.contains(&name) {
+            stale.push(relative_path_string(base_path, &dir.join(&name))?);
+        }
+    }
+
+    Ok(())
+}
+
+// Note text with invalid bytes replaced, and whether the note is damaged,
+// i.e. not valid UTF-8. A U+FFFD already in the text is valid UTF-8 and may
+// well be intended, so it isn't counted.
+fn decode_note(bytes: Vec<u8>) -> (String, bool) {
+    match String::from_utf8(bytes) {
+        Ok(content) => (content, false),
+        Err(e) => (String::from_utf8_lossy(e.as_bytes()).into_owned(), true),
+    }
+}
+
+fn integrity_report(base_path: &Path) -> Result<IntegrityReport, String> {
+    let notes = NoteIndex::build(base_path)?;
+    let files = FileIndex::build(base_path)?;
+
+    let mut report = IntegrityReport::default();
+    let mut linked: HashSet<String> = HashSet::new();
+    let mut referenced: HashSet<String> = HashSet::new();
+
+    // One read per note feeds the link, frontmatter and attachment checks
+    for note in &notes.notes {
+        let bytes = fs::read(base_path.join(note))
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+
+        let (content, is_damaged) = decode_note(bytes);
+        if is_damaged {
+            report.encoding_problems.push(note.clone());
+        }
+
+        if frontmatter_problem(&content).is_some() {
+            report.malformed_frontmatter.push(note.clone());
+        }
+
+        let mut has_broken_link = false;
+        for link in parse_links_from_content(&content, note) {
+            match notes.resolve_link_target(note, &link.target_note) {
+                Some(target) => {
+                    linked.insert(note.clone());
+                    linked.insert(target);
+                }
+                // Links to attachments aren't broken, just not note links
+                None if files.resolve(note, &link.target_note).is_some() => {}
+                None => has_broken_link = true,
+            }
+        }
+        if has_broken_link {
+            report.broken_links.push(note.clone());
+        }
+
+        referenced.extend(files.referenced_by(note, &content));
+    }
+
+    let mut by_stem: HashMap<String, Vec<&String>> = HashMap::new();
+    for note in &notes.notes {
+        by_stem.entry(note_stem(note).to_lowercase()).or_default().push(note);
+    }
+    let duplicates: BTreeSet<&String> = by_stem
+        .values()
+        .filter(|group| group.len() > 1)
+        .flatten()
+        .copied()
+        .collect();
+    report.duplicate_titles = duplicates.into_iter().cloned().collect();
+
+    report.orphan_notes = notes
+        .notes
+        .iter()
+        .filter(|note| !linked.contains(*note))
+        .cloned()
+        .collect();
+
+    collect_stale_order_entries(base_path, base_path, &mut report.stale_order_entries)?;
+    report.stale_order_entries.sort();
+
+    report.unused_attachments = files.unused_attachments(&referenced).cloned().collect();
+
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn check_vault_integrity(app_handle: tauri::AppHandle) -> Result<IntegrityReport, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    integrity_report(base_path)
+}
+
+#[tauri::command]
+pub async fn find_malformed_frontmatter(app_handle: tauri::AppHandle) -> Result<Vec<FrontmatterProblem>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    frontmatter_problems(base_path)
+}
+
+fn frontmatter_problems(base_path: &Path) -> Result<Vec<FrontmatterProblem>, String> {
+    let mut problems = Vec::new();
+
+    for path in collect_markdown_files(base_path)? {
+        if let Some(reason) = with_note_content(&path, frontmatter_problem)? {
+            problems.push(FrontmatterProblem { path: relative_path_string(base_path, &path)?, reason });
+        }
+    }
+
+    Ok(problems)
+}
+
+// Whether nothing but whitespace is left once the frontmatter is removed.
+// Zero-byte stubs and notes holding only a frontmatter block both count.
+fn is_effectively_empty(content: &str) -> bool {
+    let body = split_frontmatter(content).map(|(_, body)| body).unwrap_or(content);
+    body.trim().is_empty()
+}
+
+#[tauri::command]
+pub async fn empty_notes(app_handle: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    find_empty_notes(base_path)
+}
+
+fn find_empty_notes(base_path: &Path) -> Result<Vec<String>, String> {
+    let mut empty = Vec::new();
+
+    for path in collect_markdown_files(base_path)? {
+        if with_note_content(&path, is_effectively_empty)? {
+            empty.push(relative_path_string(base_path, &path)?);
+        }
+    }
+
+    Ok(empty)
+}
+
+// Notes that aren't valid UTF-8 (see `decode_note`).
+#[tauri::command]
+pub async fn find_encoding_issues(app_handle: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    find_undecodable_notes(base_path)
+}
+
+fn find_undecodable_notes(base_path: &Path) -> Result<Vec<String>, String> {
+    let mut damaged = Vec::new();
+
+    for path in collect_markdown_files(base_path)? {
+        let bytes = fs::read(&path)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+
+        if decode_note(bytes).1 {
+            damaged.push(relative_path_string(base_path, &path)?);
+        }
+    }
+
+    Ok(damaged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn broken_fixture_fills_every_category() {
+        let vault = TempVault::new();
+        vault.write("Hub.md", "[[Linked]] [[Nowhere]] ![[used.png]]");
+        vault.write("Linked.md", "back to [[Hub]]");
+        vault.write("Lonely.md", "no links at all");
+        vault.write("a/Dup.md", "[[Hub]]");
+        vault.write("b/Dup.md", "[[Hub]]");
+        vault.write("Broken.md", "---\ntitle: [unclosed\n---\n[[Hub]]");
+        vault.write("Latin1.md", b"caf\xe9 [[Hub]]");
+        vault.write("used.png", "png");
+        vault.write("unused.png", "png");
+        vault.write("a/.tau_order.json", r#"{"order":["Dup.md","Gone.md"]}"#);
+
+        let report = integrity_report(vault.path()).unwrap();
+
+        assert_eq!(report.broken_links, vec!["Hub.md"]);
+        assert_eq!(report.duplicate_titles, vec!["a/Dup.md", "b/Dup.md"]);
+        assert_eq!(report.orphan_notes, vec!["Lonely.md"]);
+        assert_eq!(report.malformed_frontmatter, vec!["Broken.md"]);
+        assert_eq!(report.stale_order_entries, vec!["a/Gone.md"]);
+        assert_eq!(report.unused_attachments, vec!["unused.png"]);
+        assert_eq!(report.encoding_problems, vec!["Latin1.md"]);
+    }
+
+    #[test]
+    fn healthy_vault_gives_empty_report() {
+        let vault = TempVault::new();
+        vault.write("One.md", "[[Two]]");
+        vault.write("Two.md", "[[One]]");
+
+        let report = integrity_report(vault.path()).unwrap();
+        assert!(report.broken_links.is_empty());
+        assert!(report.orphan_notes.is_empty());
+        assert!(report.encoding_problems.is_empty());
+        assert!(report.unused_attachments.is_empty());
+    }
+
+    #[test]
+    fn unterminated_and_invalid_blocks_are_reported() {
+        let vault = TempVault::new();
+        vault.write("Unterminated.md", "---\ntitle: Draft\n\nThe body never closes the block");
+        vault.write("Invalid.md", "---\ntitle: [unclosed\n---\nbody");
+        vault.write("Valid.md", "---\ntitle: Fine\n---\nbody");
+        vault.write("None.md", "No frontmatter\n---\nJust a rule");
+
+        let mut problems = frontmatter_problems(vault.path()).unwrap();
+        problems.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let paths: Vec<&str> = problems.iter().map(|problem| problem.path.as_str()).collect();
+        assert_eq!(paths, vec!["Invalid.md", "Unterminated.md"]);
+        assert!(problems[0].reason.starts_with("Invalid YAML"));
+        assert_eq!(problems[1].reason, "Frontmatter block is never closed");
+    }
+
+    #[test]
+    fn frontmatter_only_notes_are_empty_but_one_word_is_not() {
+        let vault = TempVault::new();
+        vault.write("Stub.md", "");
+        vault.write("FrontmatterOnly.md", "---\ntitle: Stub\n---\n  \n\t\n");
+        vault.write("OneWord.md", "---\ntitle: Short\n---\nword");
+
+        let mut empty = find_empty_notes(vault.path()).unwrap();
+        empty.sort();
+
+        assert_eq!(empty, vec!["FrontmatterOnly.md", "Stub.md"]);
+    }
+
+    #[test]
+    fn only_invalid_utf8_is_an_encoding_issue() {
+        let vault = TempVault::new();
+        vault.write("Invalid.md", b"caf\xe9 \xff\xfe");
+        vault.write("Replacement.md", "A literal \u{FFFD} typed on purpose");
+        vault.write("Clean.md", "café");
+
+        assert_eq!(find_undecodable_notes(vault.path()).unwrap(), vec!["Invalid.md"]);
+    }
+}
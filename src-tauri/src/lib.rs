@@ -1,4 +1,37 @@
+mod archive;
+mod attachments;
+mod config;
+mod conflicts;
+mod diff;
+mod export;
+mod folders;
+mod frontmatter;
 mod fs;
+mod git;
+mod graph;
+mod hash;
+mod health;
+mod ids;
+mod integrity;
+mod links;
+mod lock;
+mod ordering;
+mod outline;
+mod plaintext;
+mod redirects;
+mod review;
+mod search;
+mod snapshots;
+mod snippets;
+mod tags;
+mod tasks;
+#[cfg(test)]
+mod test_util;
+mod timeline;
+mod timing;
+mod trash;
+mod ui_state;
+mod watch;
 
 use std::sync::Mutex;
 
@@ -8,24 +41,130 @@ pub fn run() {
     .plugin(tauri_plugin_opener::init())
     .plugin(tauri_plugin_dialog::init())
     .manage(Mutex::new(fs::AppState::default()))
+    .manage(Mutex::new(watch::NoteWatcher::default()))
     .invoke_handler(tauri::generate_handler![
         fs::set_vault,
         fs::get_vault,
         fs::list_entries,
+        fs::list_entries_page,
+        fs::position_in_folder,
+        fs::effective_order,
         fs::create_note,
         fs::create_folder,
         fs::read_note,
         fs::write_note,
         fs::rename_entry,
+        fs::rename_note_title,
         fs::delete_entry,
+        trash::restore_from_trash,
         fs::reveal_in_os,
         fs::get_links_from_file,
         fs::get_all_links,
         fs::suggest_links,
-        fs::reorder_entries
+        fs::reorder_entries,
+        fs::reorder_multiple,
+        fs::move_to_position,
+        ordering::validate_order_files,
+        ordering::repair_order_file,
+        fs::list_extensions,
+        fs::vault_disk_usage,
+        fs::large_notes,
+        fs::all_paths,
+        archive::import_archive,
+        archive::import_directory,
+        lock::force_unlock,
+        diff::note_diff,
+        search::search_notes,
+        search::regex_search,
+        search::extract_matches,
+        search::count_term,
+        snippets::code_blocks,
+        links::get_backlinks,
+        links::get_backlinks_batch,
+        links::backlink_counts,
+        links::normalize_links,
+        links::convert_links_in_note,
+        links::get_ambiguous_links,
+        links::broken_links_by_source,
+        links::find_display_mismatches,
+        links::complete_link,
+        links::link_at_position,
+        redirects::list_redirects,
+        redirects::clear_redirect,
+        graph::get_graph,
+        graph::note_neighborhood,
+        graph::reachable_notes,
+        graph::find_cycles,
+        graph::shortest_link_path,
+        graph::longest_paths,
+        graph::related_notes,
+        graph::folder_link_density,
+        tags::suggest_tags,
+        tags::add_tag_to_notes,
+        tags::remove_tag_from_notes,
+        tags::untagged_notes,
+        tasks::toggle_task_by_text,
+        timeline::notes_created_on,
+        timeline::entries_modified_between,
+        timeline::recently_created,
+        timeline::activity_by_day,
+        folders::flatten_folder,
+        folders::empty_folder,
+        folders::collect_tag_into_folder,
+        folders::bulk_rename,
+        folders::create_project,
+        attachments::read_file_bytes,
+        attachments::write_file_bytes,
+        attachments::find_attachment_references,
+        attachments::find_unused_attachments,
+        plaintext::note_to_plaintext,
+        plaintext::word_frequency,
+        outline::get_all_headings,
+        outline::get_outline,
+        outline::suggest_headings,
+        outline::insert_toc,
+        outline::links_under_heading,
+        git::note_history,
+        git::restore_note_revision,
+        git::git_status,
+        git::git_commit,
+        conflicts::find_conflicts,
+        ui_state::get_expanded_folders,
+        ui_state::set_expanded_folders,
+        ui_state::pin_folder,
+        ui_state::get_pinned_folders,
+        export::export_bundle,
+        export::export_links,
+        export::rollup_notes,
+        export::generate_index,
+        integrity::check_vault_integrity,
+        integrity::find_malformed_frontmatter,
+        integrity::empty_notes,
+        integrity::find_encoding_issues,
+        hash::note_hash,
+        hash::vault_hashes,
+        snapshots::create_snapshot,
+        snapshots::diff_snapshot,
+        ids::assign_note_id,
+        ids::resolve_id,
+        frontmatter::read_frontmatter_batch,
+        frontmatter::query_by_frontmatter,
+        frontmatter::rename_frontmatter_key,
+        frontmatter::set_frontmatter,
+        frontmatter::frontmatter_keys,
+        review::get_due_reviews,
+        review::mark_reviewed,
+        watch::watch_note,
+        watch::unwatch_note,
+        health::vault_available,
+        config::get_vault_config,
+        config::set_vault_config
     ])
     .setup(|app| {
-      if cfg!(debug_assertions) {
+      health::spawn_health_monitor(app.handle().clone());
+
+      // Release builds opt in with TAU_LOG=1
+      if timing::logging_enabled() {
         app.handle().plugin(
           tauri_plugin_log::Builder::default()
             .level(log::LevelFilter::Info)
@@ -34,6 +173,11 @@ pub fn run() {
       }
       Ok(())
     })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(tauri::generate_context!())
+    .expect("error while building tauri application")
+    .run(|app_handle, event| {
+      if let tauri::RunEvent::Exit = event {
+        lock::release_on_exit(app_handle);
+      }
+    });
 }
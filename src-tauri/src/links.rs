@@ -0,0 +1,1107 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use tauri::Manager;
+
+use crate::attachments::FileIndex;
+use crate::frontmatter::{is_note_readonly, note_aliases, readonly_error};
+use crate::ids::{frontmatter_id, read_id_map};
+use crate::redirects::read_redirects;
+use crate::config::{read_vault_config, LinkResolution};
+use crate::fs::{calculate_similarity, collect_markdown_files, normalize_lexically, parse_links_from_content, relative_path_string, with_note_content, write_atomic, AppState, Link, LinkSuggestion};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AmbiguousLink {
+    pub link: Link,
+    // Every note the target could mean; the first is the one it resolves to
+    pub candidates: Vec<String>,
+}
+
+// Drops a `#heading` / `#^block` suffix from a link target.
+pub(crate) fn strip_link_anchor(target: &str) -> &str {
+    match target.find('#') {
+        Some(pos) => &target[..pos],
+        None => target,
+    }
+}
+
+// `[[id:...]]` links survive renames and must never be rewritten to names.
+pub(crate) fn is_id_link(target: &str) -> bool {
+    target.trim_start().starts_with("id:")
+}
+
+// Lookup key shared by link targets and note paths: anchor and `.md`
+// stripped, forward slashes, case-folded.
+fn normalize_target(target: &str) -> String {
+    let target = strip_link_anchor(target).trim().replace('\\', "/");
+    let target = target.trim_start_matches("./");
+    let lower = target.to_lowercase();
+
+    match lower.strip_suffix(".md") {
+        Some(stem) => stem.to_string(),
+        None => lower,
+    }
+}
+
+// Every markdown note in the vault, indexed for link resolution.
+pub(crate) struct NoteIndex {
+    pub(crate) notes: Vec<String>,
+    by_stem: HashMap<String, Vec<String>>,
+    by_path: HashMap<String, String>,
+    by_alias: HashMap<String, Vec<String>>,
+    by_id: HashMap<String, String>,
+    // Normalized old path -> path the note was renamed to
+    redirects: HashMap<String, String>,
+    resolution: LinkResolution,
+}
+
+impl NoteIndex {
+    pub(crate) fn build(base_path: &Path) -> Result<Self, String> {
+        let mut notes = Vec::new();
+        let mut aliases = Vec::new();
+        let mut frontmatter_ids = Vec::new();
+
+        for path in collect_markdown_files(base_path)? {
+            let note = relative_path_string(base_path, &path)?;
+            // Unreadable notes simply contribute no aliases or id
+            if let Ok(content) = fs::read_to_string(&path) {
+                for alias in note_aliases(&content) {
+                    aliases.push((alias, note.clone()));
+                }
+                if let Some(id) = frontmatter_id(&content) {
+                    frontmatter_ids.push((id, note.clone()));
+                }
+            }
+            notes.push(note);
+        }
+
+        let mut index = Self::from_paths(notes);
+        index.resolution = read_vault_config(base_path).link_resolution;
+        for (alias, note) in aliases {
+            index.add_alias(&alias, note);
+        }
+
+        // The id map is authoritative; ids only found in frontmatter cover
+        // notes moved outside the app, and stale map entries are ignored
+        for (id, path) in read_id_map(base_path).ids {
+            if let Some(note) = index.by_path.get(&normalize_target(&path)) {
+                index.by_id.insert(id, note.clone());
+            }
+        }
+        for (id, note) in frontmatter_ids {
+            index.by_id.entry(id).or_insert(note);
+        }
+
+        index.redirects = read_redirects(base_path)
+            .redirects
+            .into_iter()
+            .map(|(old, new)| (normalize_target(&old), new))
+            .collect();
+
+        Ok(index)
+    }
+
+    fn add_alias(&mut self, alias: &str, note: String) {
+        let candidates = self.by_alias.entry(alias.trim().to_lowercase()).or_default();
+        if !candidates.contains(&note) {
+            candidates.push(note);
+        }
+    }
+
+    pub(crate) fn from_paths(notes: Vec<String>) -> Self {
+        let mut by_stem: HashMap<String, Vec<String>> = HashMap::new();
+        let mut by_path = HashMap::new();
+
+        for note in &notes {
+            let key = normalize_target(note);
+            let stem = key.rsplit('/').next().unwrap_or(&key).to_string();
+            by_stem.entry(stem).or_default().push(note.clone());
+            by_path.insert(key, note.clone());
+        }
+
+        // Shallowest path first so an ambiguous stem resolves predictably
+        for candidates in by_stem.values_mut() {
+            candidates.sort_by(|a, b| {
+                let depth = |p: &str| p.matches(['/', '\\']).count();
+                depth(a).cmp(&depth(b)).then_with(|| a.cmp(b))
+            });
+        }
+
+        NoteIndex { notes, by_stem, by_path, by_alias: HashMap::new(), by_id: HashMap::new(), redirects: HashMap::new(), resolution: LinkResolution::Global }
+    }
+
+    // All notes a link target in `source_file` could refer to. `id:` targets
+    // and path-qualified targets match at most one note; bare names match
+    // every note sharing that stem, and fall back to frontmatter aliases when
+    // no filename matches. An empty `source_file` means the vault root.
+    pub(crate) fn candidates(&self, source_file: &str, target: &str) -> Vec<String> {
+        if is_id_link(target) {
+            let id = strip_link_anchor(target).trim().strip_prefix("id:").unwrap_or("");
+            return self.by_id.get(id.trim()).cloned().into_iter().collect();
+        }
+
+        if self.resolution == LinkResolution::Relative {
+            if let Some(note) = self.resolve_relative(source_file, target) {
+                return vec![note];
+            }
+        }
+
+        let key = normalize_target(target);
+
+        if key.is_empty() {
+            return Vec::new();
+        }
+
+        if key.contains('/') {
+            return match self.by_path.get(&key) {
+                Some(note) => vec![note.clone()],
+                None => self.follow_redirect(&key).into_iter().collect(),
+            };
+        }
+
+        if let Some(candidates) = self.by_stem.get(&key) {
+            return candidates.clone();
+        }
+
+        // Aliases are matched verbatim, so `.md` stripping doesn't apply
+        let alias_key = strip_link_anchor(target).trim().to_lowercase();
+        if let Some(candidates) = self.by_alias.get(&alias_key) {
+            return candidates.clone();
+        }
+
+        // A bare name can only mean a renamed note by its old stem
+        let mut redirected: Vec<String> = self
+            .redirects
+            .keys()
+            .filter(|old| old.rsplit('/').next() == Some(key.as_str()))
+            .filter_map(|old| self.follow_redirect(old))
+            .collect();
+        redirected.sort();
+        redirected.dedup();
+        redirected
+    }
+
+    // Follows redirects from the normalized old path `key` until one lands
+    // on an existing note. Chains (A -> B -> C) are followed; cycles and
+    // chains ending at a missing note give None.
+    fn follow_redirect(&self, key: &str) -> Option<String> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut current = key.to_string();
+
+        while seen.insert(current.clone()) {
+            let next = normalize_target(self.redirects.get(&current)?);
+            if let Some(note) = self.by_path.get(&next) {
+                return Some(note.clone());
+            }
+            current = next;
+        }
+
+        None
+    }
+
+    // The target read as a path from the source note's folder.
+    fn resolve_relative(&self, source_file: &str, target: &str) -> Option<String> {
+        let source_dir = Path::new(source_file).parent().unwrap_or(Path::new(""));
+        let target = strip_link_anchor(target).trim().replace('\\', "/");
+        let joined = normalize_lexically(&source_dir.join(target))?;
+        self.by_path.get(&normalize_target(&joined.to_string_lossy())).cloned()
+    }
+
+    pub(crate) fn resolve_link_target(&self, source_file: &str, target: &str) -> Option<String> {
+        self.candidates(source_file, target).into_iter().next()
+    }
+
+    // Frontmatter aliases grouped by the note that declares them.
+    pub(crate) fn aliases_by_note(&self) -> HashMap<&String, Vec<&String>> {
+        let mut aliases: HashMap<&String, Vec<&String>> = HashMap::new();
+        for (alias, notes) in &self.by_alias {
+            for note in notes {
+                aliases.entry(note).or_default().push(alias);
+            }
+        }
+        aliases
+    }
+}
+
+pub(crate) fn collect_vault_links(base_path: &Path, index: &NoteIndex) -> Result<Vec<Link>, String> {
+    let mut links = Vec::new();
+
+    for note in &index.notes {
+        links.extend(with_note_content(&base_path.join(note), |content| {
+            parse_links_from_content(content, note)
+        })?);
+    }
+
+    Ok(links)
+}
+
+// Backlinks for each requested target from a single pass over the vault.
+// Targets that don't resolve to a note map to an empty list.
+fn backlinks_for_targets(base_path: &Path, targets: &[String]) -> Result<BTreeMap<String, Vec<Link>>, String> {
+    let index = NoteIndex::build(base_path)?;
+
+    let mut wanted: HashMap<String, Vec<&String>> = HashMap::new();
+    let mut result = BTreeMap::new();
+
+    for target in targets {
+        // A repeated target would otherwise collect every backlink twice
+        if result.insert(target.clone(), Vec::new()).is_some() {
+            continue;
+        }
+        if let Some(path) = index.resolve_link_target("", target) {
+            wanted.entry(path).or_default().push(target);
+        }
+    }
+
+    for link in collect_vault_links(base_path, &index)? {
+        let resolved = match index.resolve_link_target(&link.source_file, &link.target_note) {
+            Some(resolved) => resolved,
+            None => continue,
+        };
+
+        if let Some(requested) = wanted.get(&resolved) {
+            for target in requested {
+                if let Some(links) = result.get_mut(*target) {
+                    links.push(link.clone());
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn get_backlinks(app_handle: tauri::AppHandle, target: String) -> Result<Vec<Link>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let mut backlinks = backlinks_for_targets(base_path, std::slice::from_ref(&target))?;
+    Ok(backlinks.remove(&target).unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn get_backlinks_batch(app_handle: tauri::AppHandle, targets: Vec<String>) -> Result<BTreeMap<String, Vec<Link>>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    backlinks_for_targets(base_path, &targets)
+}
+
+// Incoming resolved links per note, counted the same way as get_backlinks
+// so a badge always matches the backlinks panel. Every note is present,
+// with 0 when nothing links to it.
+#[tauri::command]
+pub async fn backlink_counts(app_handle: tauri::AppHandle) -> Result<HashMap<String, usize>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    count_backlinks(base_path)
+}
+
+fn count_backlinks(base_path: &Path) -> Result<HashMap<String, usize>, String> {
+    let index = NoteIndex::build(base_path)?;
+    let mut counts: HashMap<String, usize> = index.notes.iter().map(|note| (note.clone(), 0)).collect();
+
+    for link in collect_vault_links(base_path, &index)? {
+        if let Some(resolved) = index.resolve_link_target(&link.source_file, &link.target_note) {
+            *counts.entry(resolved).or_insert(0) += 1;
+        }
+    }
+
+    Ok(counts)
+}
+
+// Rewrites the target of every wikilink in `content` for which `replace`
+// returns a new target. Heading/block anchors and display text are kept.
+// Returns the new content and how many links changed.
+pub(crate) fn rewrite_links<F>(content: &str, mut replace: F) -> (String, usize)
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let mut output = String::with_capacity(content.len());
+    let mut last_end = 0;
+    let mut changed = 0;
+
+    for link in parse_links_from_content(content, "") {
+        let target = strip_link_anchor(&link.target_note);
+        let anchor = &link.target_note[target.len()..];
+
+        let new_target = match replace(target.trim()) {
+            Some(new_target) if new_target != target.trim() => new_target,
+            _ => continue,
+        };
+
+        output.push_str(&content[last_end..link.position]);
+        output.push_str("[[");
+        output.push_str(&new_target);
+        output.push_str(anchor);
+        if let Some(display) = &link.display_text {
+            output.push('|');
+            output.push_str(display);
+        }
+        output.push_str("]]");
+
+        last_end = link.position + link.length;
+        changed += 1;
+    }
+
+    output.push_str(&content[last_end..]);
+    (output, changed)
+}
+
+pub(crate) fn note_stem(note_path: &str) -> &str {
+    let name = note_path.rsplit(['/', '\\']).next().unwrap_or(note_path);
+    name.strip_suffix(".md").unwrap_or(name)
+}
+
+// Vault-relative path with forward slashes and without the `.md` extension.
+pub(crate) fn note_link_path(note_path: &str) -> String {
+    let path = note_path.replace('\\', "/");
+    match path.strip_suffix(".md") {
+        Some(stripped) => stripped.to_string(),
+        None => path,
+    }
+}
+
+fn canonical_target(index: &NoteIndex, resolved: &str, style: &str) -> Option<String> {
+    match style {
+        // Only shorten to the stem when that still resolves to the same note
+        "stem" => {
+            let stem = note_stem(resolved);
+            (index.candidates("", stem).len() == 1).then(|| stem.to_string())
+        }
+        "path" => Some(note_link_path(resolved)),
+        _ => None,
+    }
+}
+
+// `resolved` written from `note` in `style`, or None when that form would
+// resolve to a different note.
+fn styled_target(index: &NoteIndex, note: &str, resolved: &str, style: &str) -> Option<String> {
+    let converted = match style {
+        "relative" => relative_link_path(note, resolved),
+        style => canonical_target(index, resolved, style)?,
+    };
+    (index.resolve_link_target(note, &converted).as_deref() == Some(resolved)).then_some(converted)
+}
+
+// Rewrites every resolvable link in the vault to `style`. Read-only notes
+// are left alone. Returns how many links changed.
+fn normalize_vault_links(base_path: &Path, style: &str) -> Result<usize, String> {
+    let index = NoteIndex::build(base_path)?;
+    let mut total_changed = 0;
+
+    if style == "relative" && index.resolution != LinkResolution::Relative {
+        return Err("Relative links need relative link resolution in the vault config".to_string());
+    }
+
+    for note in &index.notes {
+        let file_path = base_path.join(note);
+
+        if is_note_readonly(&file_path) {
+            log::info!("Skipping read-only note {} while normalizing links", note);
+            continue;
+        }
+
+        // A lossy read would corrupt the note when written back, so skip it
+        let content = match fs::read_to_string(&file_path) {
+            Ok(content) => content,
+            Err(e) => {
+                log::warn!("Skipping {} while normalizing links: {}", note, e);
+                continue;
+            }
+        };
+
+        let (rewritten, changed) = rewrite_links(&content, |target| {
+            if is_id_link(target) {
+                return None;
+            }
+            let resolved = index.resolve_link_target(note, target)?;
+            styled_target(&index, note, &resolved, style)
+        });
+
+        if changed > 0 {
+            write_atomic(&file_path, rewritten.as_bytes())
+                .map_err(|e| format!("Failed to write note: {}", e))?;
+            total_changed += changed;
+        }
+    }
+
+    Ok(total_changed)
+}
+
+#[tauri::command]
+pub async fn normalize_links(app_handle: tauri::AppHandle, style: String) -> Result<usize, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    if !matches!(style.as_str(), "stem" | "relative" | "path") {
+        return Err(format!("Unknown link style '{}', expected 'stem', 'relative' or 'path'", style));
+    }
+
+    normalize_vault_links(base_path, &style)
+}
+
+// `target` as a path from `source_file`'s folder, e.g. `../other/note`.
+// Same-folder targets get a `./` prefix so they don't read as bare stems.
+fn relative_link_path(source_file: &str, target: &str) -> String {
+    let source_dir: Vec<&str> = source_file.split('/').collect();
+    let source_dir = &source_dir[..source_dir.len() - 1];
+    let target_path = note_link_path(target);
+    let target_parts: Vec<&str> = target_path.split('/').collect();
+
+    let common = source_dir
+        .iter()
+        .zip(&target_parts)
+        .take_while(|(a, b)| a == b)
+        .count()
+        .min(target_parts.len() - 1);
+
+    let mut parts: Vec<&str> = vec![".."; source_dir.len() - common];
+    if parts.is_empty() {
+        parts.push(".");
+    }
+    parts.extend(&target_parts[common..]);
+    parts.join("/")
+}
+
+// Rewrites the links in one note to `to_style`: "stem", "relative" (from the
+// note's folder) or "path" (from the vault root). A link is only changed when
+// the new form still resolves to the same note.
+fn convert_note_links(base_path: &Path, rel: &str, to_style: &str) -> Result<usize, String> {
+    if !matches!(to_style, "stem" | "relative" | "path") {
+        return Err(format!("Unknown link style '{}', expected 'stem', 'relative' or 'path'", to_style));
+    }
+
+    let file_path = base_path.join(rel);
+
+    if !file_path.is_file() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    if is_note_readonly(&file_path) {
+        return Err(readonly_error(rel));
+    }
+
+    let index = NoteIndex::build(base_path)?;
+
+    if to_style == "relative" && index.resolution != LinkResolution::Relative {
+        return Err("Relative links need relative link resolution in the vault config".to_string());
+    }
+
+    // Strict read so a note with invalid UTF-8 isn't rewritten lossily
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read note: {}", e))?;
+
+    let note = rel.replace('\\', "/");
+    let (rewritten, changed) = rewrite_links(&content, |target| {
+        if is_id_link(target) {
+            return None;
+        }
+        let resolved = index.resolve_link_target(&note, target)?;
+        styled_target(&index, &note, &resolved, to_style)
+    });
+
+    if changed > 0 {
+        write_atomic(&file_path, rewritten.as_bytes())
+            .map_err(|e| format!("Failed to write note: {}", e))?;
+    }
+
+    Ok(changed)
+}
+
+#[tauri::command]
+pub async fn convert_links_in_note(app_handle: tauri::AppHandle, rel: String, to_style: String) -> Result<usize, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    convert_note_links(base_path, &rel, &to_style)
+}
+
+#[tauri::command]
+pub async fn get_ambiguous_links(app_handle: tauri::AppHandle, rel: String) -> Result<Vec<AmbiguousLink>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    ambiguous_links(base_path, &rel)
+}
+
+fn ambiguous_links(base_path: &Path, rel: &str) -> Result<Vec<AmbiguousLink>, String> {
+    let file_path = base_path.join(rel);
+
+    if !file_path.exists() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    let index = NoteIndex::build(base_path)?;
+    let links = with_note_content(&file_path, |content| parse_links_from_content(content, rel))?;
+
+    Ok(links
+        .into_iter()
+        .filter_map(|link| {
+            let candidates = index.candidates(rel, &link.target_note);
+            (candidates.len() > 1).then_some(AmbiguousLink { link, candidates })
+        })
+        .collect())
+}
+
+// Links that resolve to neither a note nor an attachment, keyed by the note
+// containing them. Notes without broken links are left out.
+#[tauri::command]
+pub async fn broken_links_by_source(app_handle: tauri::AppHandle) -> Result<HashMap<String, Vec<Link>>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    broken_links_grouped(base_path)
+}
+
+fn broken_links_grouped(base_path: &Path) -> Result<HashMap<String, Vec<Link>>, String> {
+    let index = NoteIndex::build(base_path)?;
+    let files = FileIndex::build(base_path)?;
+    let mut broken: HashMap<String, Vec<Link>> = HashMap::new();
+
+    for link in collect_vault_links(base_path, &index)? {
+        let resolves = index.resolve_link_target(&link.source_file, &link.target_note).is_some()
+            || files.resolve(&link.source_file, &link.target_note).is_some();
+
+        if !resolves {
+            broken.entry(link.source_file.clone()).or_default().push(link);
+        }
+    }
+
+    Ok(broken)
+}
+
+// Below this, a link's display text is considered unrelated to its target.
+const DISPLAY_MATCH_THRESHOLD: f64 = 0.3;
+
+fn title_words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_string())
+        .collect()
+}
+
+// 1.0 when one contains the other ("the budget" for `budget`, "my note" for
+// `my-note`), otherwise the share of distinct words the two have in common.
+fn display_similarity(display: &str, title: &str) -> f64 {
+    let display_words = title_words(display);
+    let target_words = title_words(title);
+
+    if display_words.is_empty() || target_words.is_empty() {
+        return 0.0;
+    }
+
+    let display_joined = display_words.join(" ");
+    let target_joined = target_words.join(" ");
+    if display_joined.contains(&target_joined) || target_joined.contains(&display_joined) {
+        return 1.0;
+    }
+
+    let display_set: HashSet<&String> = display_words.iter().collect();
+    let target_set: HashSet<&String> = target_words.iter().collect();
+    let shared = display_set.intersection(&target_set).count();
+    shared as f64 / display_set.union(&target_set).count() as f64
+}
+
+// Resolved links whose display text looks unrelated to what they point at:
+// not close to the target's name, any of its aliases, or the linked heading.
+#[tauri::command]
+pub async fn find_display_mismatches(app_handle: tauri::AppHandle) -> Result<Vec<Link>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    display_mismatches(base_path)
+}
+
+fn display_mismatches(base_path: &Path) -> Result<Vec<Link>, String> {
+    let index = NoteIndex::build(base_path)?;
+    let aliases = index.aliases_by_note();
+    let mut mismatches = Vec::new();
+
+    for link in collect_vault_links(base_path, &index)? {
+        let display = match &link.display_text {
+            Some(display) if !display.trim().is_empty() => display,
+            _ => continue,
+        };
+
+        let resolved = match index.resolve_link_target(&link.source_file, &link.target_note) {
+            Some(resolved) => resolved,
+            None => continue,
+        };
+
+        let anchor = link.target_note[strip_link_anchor(&link.target_note).len()..].trim_start_matches(['#', '^']);
+        let mut titles: Vec<&str> = vec![note_stem(&resolved), anchor];
+        titles.extend(aliases.get(&resolved).into_iter().flatten().map(|alias| alias.as_str()));
+
+        let best = titles
+            .iter()
+            .map(|title| display_similarity(display, title))
+            .fold(0.0, f64::max);
+
+        if best < DISPLAY_MATCH_THRESHOLD {
+            mismatches.push(link);
+        }
+    }
+
+    Ok(mismatches)
+}
+
+// Ranking bonuses for `complete_link` when the linking note is known.
+const SAME_FOLDER_BOOST: f64 = 0.2;
+const ALREADY_LINKED_BOOST: f64 = 0.1;
+
+// Completions for a half-typed `[[partial`, scored on stems and aliases with
+// the same fuzzy scorer as `suggest_links`. With `from_note`, notes in its
+// folder and notes it already links to rank higher.
+#[tauri::command]
+pub async fn complete_link(app_handle: tauri::AppHandle, partial: String, from_note: Option<String>) -> Result<Vec<LinkSuggestion>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    link_completions(base_path, &partial, from_note.as_deref())
+}
+
+fn link_completions(base_path: &Path, partial: &str, from_note: Option<&str>) -> Result<Vec<LinkSuggestion>, String> {
+    let partial = partial.trim();
+    if partial.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let index = NoteIndex::build(base_path)?;
+    let aliases = index.aliases_by_note();
+
+    let from_folder = from_note.map(|note| Path::new(note).parent().unwrap_or(Path::new("")));
+    let linked: Vec<String> = match from_note {
+        Some(note) if base_path.join(note).is_file() => with_note_content(&base_path.join(note), |content| {
+            parse_links_from_content(content, note)
+                .iter()
+                .filter_map(|link| index.resolve_link_target(note, &link.target_note))
+                .collect()
+        })?,
+        _ => Vec::new(),
+    };
+
+    let mut suggestions: Vec<LinkSuggestion> = Vec::new();
+
+    for note in &index.notes {
+        if from_note == Some(note.as_str()) {
+            continue;
+        }
+
+        let stem_score = calculate_similarity(partial, note_stem(note));
+        let alias_score = aliases
+            .get(note)
+            .into_iter()
+            .flatten()
+            .map(|alias| calculate_similarity(partial, alias))
+            .fold(0.0, f64::max);
+        let mut score = stem_score.max(alias_score);
+
+        // Same threshold as suggest_links, applied before any boosts
+        if score <= 0.1 {
+            continue;
+        }
+
+        if from_folder.is_some_and(|folder| Path::new(note).parent() == Some(folder)) {
+            score += SAME_FOLDER_BOOST;
+        }
+        if linked.contains(note) {
+            score += ALREADY_LINKED_BOOST;
+        }
+
+        suggestions.push(LinkSuggestion {
+            note_name: note_stem(note).to_string(),
+            note_path: note.clone(),
+            similarity_score: score,
+        });
+    }
+
+    suggestions.sort_by(|a, b| {
+        b.similarity_score
+            .partial_cmp(&a.similarity_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.note_path.cmp(&b.note_path))
+    });
+    suggestions.truncate(10);
+
+    Ok(suggestions)
+}
+
+// Where the link under the cursor goes. `resolved_path` is None for a
+// dangling link; `heading` / `block` carry a `#Heading` or `#^block` anchor.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResolvedLink {
+    pub link: Link,
+    pub resolved_path: Option<String>,
+    pub heading: Option<String>,
+    pub block: Option<String>,
+}
+
+// The link whose `[[...]]` span contains byte `offset`, from the opening
+// bracket up to but not including the position after the closing `]]`.
+#[tauri::command]
+pub async fn link_at_position(app_handle: tauri::AppHandle, rel: String, offset: usize) -> Result<Option<ResolvedLink>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    resolve_link_at(base_path, &rel, offset)
+}
+
+fn resolve_link_at(base_path: &Path, rel: &str, offset: usize) -> Result<Option<ResolvedLink>, String> {
+    let file_path = base_path.join(rel);
+    if !file_path.is_file() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    let link = with_note_content(&file_path, |content| {
+        parse_links_from_content(content, rel)
+            .into_iter()
+            .find(|link| link.position <= offset && offset < link.position + link.length)
+    })?;
+
+    let link = match link {
+        Some(link) => link,
+        None => return Ok(None),
+    };
+
+    let index = NoteIndex::build(base_path)?;
+    let resolved_path = index.resolve_link_target(rel, &link.target_note);
+
+    let anchor = link.target_note[strip_link_anchor(&link.target_note).len()..]
+        .trim_start_matches('#')
+        .trim();
+    let (heading, block) = match anchor.strip_prefix('^') {
+        Some(block) => (None, Some(block.to_string())),
+        None if !anchor.is_empty() => (Some(anchor.to_string()), None),
+        None => (None, None),
+    };
+
+    Ok(Some(ResolvedLink { link, resolved_path, heading, block }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    const MIXED: &str = "[[Foo]] [[Projects/Foo.md]] [[Foo.md|shown]] [[Projects/Foo#Intro]] [[a/Dup]] [[Missing]]";
+
+    fn mixed_vault() -> TempVault {
+        let vault = TempVault::new();
+        vault.write("Projects/Foo.md", "# Intro");
+        vault.write("a/Dup.md", "");
+        vault.write("b/Dup.md", "");
+        vault.write("Mixed.md", MIXED);
+        vault.write("Locked.md", format!("---\nreadonly: true\n---\n{}", MIXED));
+        vault
+    }
+
+    #[test]
+    fn normalizes_to_stem() {
+        let vault = mixed_vault();
+
+        // Ambiguous stems and unresolved links are left as they are
+        assert_eq!(normalize_vault_links(vault.path(), "stem").unwrap(), 3);
+        assert_eq!(vault.read("Mixed.md"), "[[Foo]] [[Foo]] [[Foo|shown]] [[Foo#Intro]] [[a/Dup]] [[Missing]]");
+        assert!(vault.read("Locked.md").ends_with(MIXED));
+    }
+
+    #[test]
+    fn normalizes_to_path() {
+        let vault = mixed_vault();
+
+        assert_eq!(normalize_vault_links(vault.path(), "path").unwrap(), 3);
+        assert_eq!(
+            vault.read("Mixed.md"),
+            "[[Projects/Foo]] [[Projects/Foo]] [[Projects/Foo|shown]] [[Projects/Foo#Intro]] [[a/Dup]] [[Missing]]"
+        );
+        assert!(vault.read("Locked.md").ends_with(MIXED));
+    }
+
+    #[test]
+    fn normalizes_to_relative() {
+        let vault = TempVault::new();
+        vault.write(".tau_config.json", r#"{"link_resolution":"relative"}"#);
+        vault.write("Projects/Foo.md", "");
+        vault.write("Notes/Other.md", "");
+        vault.write("Projects/Plan.md", "[[Foo]] [[Projects/Foo.md|shown]] [[Notes/Other#Top]] [[./Foo]]");
+
+        assert_eq!(normalize_vault_links(vault.path(), "relative").unwrap(), 3);
+        assert_eq!(vault.read("Projects/Plan.md"), "[[./Foo]] [[./Foo|shown]] [[../Notes/Other#Top]] [[./Foo]]");
+    }
+
+    #[test]
+    fn relative_style_needs_relative_resolution() {
+        let vault = mixed_vault();
+        assert!(normalize_vault_links(vault.path(), "relative").is_err());
+        assert_eq!(vault.read("Mixed.md"), MIXED);
+    }
+
+    #[test]
+    fn undecodable_note_is_skipped_when_normalizing() {
+        let vault = mixed_vault();
+        vault.write("Latin1.md", b"caf\xe9 [[Projects/Foo.md]]");
+
+        assert_eq!(normalize_vault_links(vault.path(), "stem").unwrap(), 3);
+        assert_eq!(fs::read(vault.path().join("Latin1.md")).unwrap(), b"caf\xe9 [[Projects/Foo.md]]");
+    }
+
+    #[test]
+    fn converts_stem_links_to_root_paths_in_a_subfolder() {
+        let vault = TempVault::new();
+        vault.write("Projects/Plan.md", "See [[Foo|the foo]] and [[Bar#Intro]], not [[Missing]]");
+        vault.write("Projects/Foo.md", "");
+        vault.write("Archive/Bar.md", "# Intro");
+        vault.write("Other.md", "[[Foo]]");
+
+        assert_eq!(convert_note_links(vault.path(), "Projects/Plan.md", "path").unwrap(), 2);
+        assert_eq!(
+            vault.read("Projects/Plan.md"),
+            "See [[Projects/Foo|the foo]] and [[Archive/Bar#Intro]], not [[Missing]]"
+        );
+        assert_eq!(vault.read("Other.md"), "[[Foo]]");
+    }
+
+    #[test]
+    fn repeated_batch_targets_list_each_backlink_once() {
+        let vault = TempVault::new();
+        vault.write("Target.md", "");
+        vault.write("Source.md", "[[Target]] and [[Target|again]]");
+
+        let targets = vec!["Target".to_string(), "Target".to_string(), "Target.md".to_string(), "Missing".to_string()];
+        let backlinks = backlinks_for_targets(vault.path(), &targets).unwrap();
+
+        assert_eq!(backlinks.len(), 3);
+        assert_eq!(backlinks["Target"].len(), 2);
+        assert_eq!(backlinks["Target.md"].len(), 2);
+        assert!(backlinks["Missing"].is_empty());
+    }
+
+    #[test]
+    fn normalizing_twice_changes_nothing() {
+        let vault = mixed_vault();
+
+        normalize_vault_links(vault.path(), "stem").unwrap();
+        assert_eq!(normalize_vault_links(vault.path(), "stem").unwrap(), 0);
+    }
+
+    #[test]
+    fn backlink_through_an_alias_only() {
+        let vault = TempVault::new();
+        vault.write("Personal Knowledge Management.md", "---\naliases: [PKM, Second Brain]\n---\n");
+        vault.write("Source.md", "See [[pkm]] and [[Second Brain|this]]");
+        vault.write("Other.md", "[[Knowledge]]");
+
+        let target = "Personal Knowledge Management.md".to_string();
+        let backlinks = backlinks_for_targets(vault.path(), std::slice::from_ref(&target)).unwrap();
+
+        let found: Vec<(&str, &str)> = backlinks[&target]
+            .iter()
+            .map(|link| (link.source_file.as_str(), link.target_note.as_str()))
+            .collect();
+        assert_eq!(found, vec![("Source.md", "pkm"), ("Source.md", "Second Brain")]);
+    }
+
+    #[test]
+    fn id_links_follow_a_renamed_target() {
+        let vault = TempVault::new();
+        vault.write("Target.md", "---\nid: 4f1c9a\n---\n# Target");
+        vault.write("Source.md", "[[id:4f1c9a|the target]] [[id:unknown]]");
+        // The map still points at the old path, as if the rename happened elsewhere
+        vault.write(".tau_ids.json", r#"{"ids":{"4f1c9a":"Target.md"}}"#);
+
+        fs::create_dir(vault.path().join("Archive")).unwrap();
+        fs::rename(vault.path().join("Target.md"), vault.path().join("Archive/Renamed.md")).unwrap();
+
+        let target = "Archive/Renamed.md".to_string();
+        let backlinks = backlinks_for_targets(vault.path(), std::slice::from_ref(&target)).unwrap();
+        assert_eq!(backlinks[&target].len(), 1);
+        assert_eq!(backlinks[&target][0].target_note, "id:4f1c9a");
+
+        let index = NoteIndex::build(vault.path()).unwrap();
+        assert_eq!(index.resolve_link_target("Source.md", "id:unknown"), None);
+    }
+
+    #[test]
+    fn link_matching_notes_in_two_folders_is_ambiguous() {
+        let vault = TempVault::new();
+        vault.write("Work/Meeting.md", "");
+        vault.write("Home/Meeting.md", "");
+        vault.write("Unique.md", "");
+        vault.write("Index.md", "[[Meeting]] [[Work/Meeting]] [[Unique]] [[Missing]]");
+
+        let ambiguous = ambiguous_links(vault.path(), "Index.md").unwrap();
+
+        assert_eq!(ambiguous.len(), 1);
+        assert_eq!(ambiguous[0].link.target_note, "Meeting");
+        let mut candidates = ambiguous[0].candidates.clone();
+        candidates.sort();
+        assert_eq!(candidates, vec!["Home/Meeting.md", "Work/Meeting.md"]);
+    }
+
+    #[test]
+    fn relative_links_prefer_the_sibling() {
+        let vault = TempVault::new();
+        vault.write("Projects/Alpha/Plan.md", "");
+        vault.write("Projects/Alpha/Notes.md", "");
+        vault.write("Archive/Plan.md", "");
+        vault.write("Projects/Other/Notes.md", "");
+
+        let index = |resolution: &str| {
+            vault.write(".tau_config.json", format!(r#"{{"link_resolution":"{}"}}"#, resolution));
+            NoteIndex::build(vault.path()).unwrap()
+        };
+
+        let relative = index("relative");
+        let resolve = |target: &str| relative.resolve_link_target("Projects/Alpha/Notes.md", target);
+        assert_eq!(resolve("./Plan").as_deref(), Some("Projects/Alpha/Plan.md"));
+        assert_eq!(resolve("Plan").as_deref(), Some("Projects/Alpha/Plan.md"));
+        assert_eq!(resolve("../Other/Notes").as_deref(), Some("Projects/Other/Notes.md"));
+        assert_eq!(resolve("../../Archive/Plan").as_deref(), Some("Archive/Plan.md"));
+
+        // Global mode ignores the source folder and picks the first same-named note
+        let global = index("global");
+        assert_eq!(global.resolve_link_target("Projects/Alpha/Notes.md", "./Plan").as_deref(), Some("Archive/Plan.md"));
+    }
+
+    #[test]
+    fn completions_rank_the_same_folder_first() {
+        let vault = TempVault::new();
+        vault.write("Archive/Budget.md", "");
+        vault.write("Work/Budget.md", "");
+        vault.write("Work/Standup.md", "");
+
+        let suggestions = link_completions(vault.path(), "budg", Some("Work/Standup.md")).unwrap();
+        let paths: Vec<&str> = suggestions.iter().map(|s| s.note_path.as_str()).collect();
+        assert_eq!(paths, vec!["Work/Budget.md", "Archive/Budget.md"]);
+
+        // Without a source note the tie falls back to path order
+        let suggestions = link_completions(vault.path(), "budg", None).unwrap();
+        assert_eq!(suggestions[0].note_path, "Archive/Budget.md");
+    }
+
+    #[test]
+    fn backlink_counts_match_a_manual_tally() {
+        let vault = TempVault::new();
+        vault.write("Hub.md", "[[Spoke]] [[Spoke#Intro]] [[Leaf]] [[Missing]]");
+        vault.write("Spoke.md", "# Intro\n[[Hub]] [[Leaf|the leaf]]");
+        vault.write("Leaf.md", "[[Leaf]]");
+        vault.write("Lonely.md", "");
+
+        let counts = count_backlinks(vault.path()).unwrap();
+
+        let expected: HashMap<String, usize> = [("Hub.md", 1), ("Spoke.md", 2), ("Leaf.md", 3), ("Lonely.md", 0)]
+            .into_iter()
+            .map(|(note, count)| (note.to_string(), count))
+            .collect();
+        assert_eq!(counts, expected);
+
+        // Each count agrees with the backlinks panel for the same note
+        let notes: Vec<String> = expected.keys().cloned().collect();
+        let backlinks = backlinks_for_targets(vault.path(), &notes).unwrap();
+        for note in &notes {
+            assert_eq!(backlinks.get(note).map_or(0, |links| links.len()), counts[note], "{}", note);
+        }
+    }
+
+    #[test]
+    fn link_at_position_covers_exactly_the_bracket_span() {
+        let vault = TempVault::new();
+        vault.write("Target.md", "# Setup");
+        // The span of `[[Target#Setup|here]]` is bytes 4..25
+        vault.write("Note.md", "See [[Target#Setup|here]] now");
+
+        let at = |offset| resolve_link_at(vault.path(), "Note.md", offset).unwrap();
+
+        assert!(at(3).is_none());
+        let first = at(4).unwrap();
+        assert_eq!(first.resolved_path.as_deref(), Some("Target.md"));
+        assert_eq!(first.heading.as_deref(), Some("Setup"));
+        assert_eq!(at(24).unwrap().link.target_note, "Target#Setup");
+        assert!(at(25).is_none());
+    }
+
+    #[test]
+    fn broken_links_are_grouped_by_source() {
+        let vault = TempVault::new();
+        vault.write("Real.md", "");
+        vault.write("diagram.png", "");
+        vault.write("Clean.md", "[[Real]] ![[diagram.png]]");
+        vault.write("Messy.md", "[[Gone]] [[Real]] [[Also Gone#Intro]]");
+
+        let broken = broken_links_grouped(vault.path()).unwrap();
+
+        assert_eq!(broken.len(), 1);
+        let targets: Vec<&str> = broken["Messy.md"].iter().map(|link| link.target_note.as_str()).collect();
+        assert_eq!(targets, vec!["Gone", "Also Gone#Intro"]);
+    }
+
+    #[test]
+    fn unrelated_display_text_is_a_mismatch() {
+        let vault = TempVault::new();
+        vault.write("Quarterly Budget.md", "---\naliases: [Money Plan]\n---\n# Forecast");
+        vault.write(
+            "Index.md",
+            "[[Quarterly Budget|the budget]] [[Quarterly Budget|money plan]] [[Quarterly Budget#Forecast|forecast]] [[Quarterly Budget|holiday photos]]",
+        );
+
+        let mismatches = display_mismatches(vault.path()).unwrap();
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].display_text.as_deref(), Some("holiday photos"));
+    }
+}
@@ -0,0 +1,193 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::fs::AppState;
+
+const LOCK_FILE_NAME: &str = ".tau_lock";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VaultLock {
+    pub pid: u32,
+    pub timestamp: u64,
+}
+
+fn get_lock_file_path(vault_path: &Path) -> PathBuf {
+    vault_path.join(LOCK_FILE_NAME)
+}
+
+fn read_lock(vault_path: &Path) -> Option<VaultLock> {
+    let content = fs::read_to_string(get_lock_file_path(vault_path)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+#[cfg(unix)]
+fn is_process_alive(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(windows)]
+fn is_process_alive(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+        .unwrap_or(false)
+}
+
+// A lock is only honoured while the process that wrote it is still running.
+// Unreadable lock files are treated the same as stale ones.
+fn is_lock_live(lock: &VaultLock) -> bool {
+    lock.pid != std::process::id() && is_process_alive(lock.pid)
+}
+
+fn write_lock(vault_path: &Path) -> Result<(), String> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| format!("Failed to convert time: {}", e))?
+        .as_secs();
+    let lock = VaultLock { pid: std::process::id(), timestamp };
+    let content = serde_json::to_string_pretty(&lock)
+        .map_err(|e| format!("Failed to serialize lock: {}", e))?;
+
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(get_lock_file_path(vault_path))
+        .map_err(|e| format!("Failed to create lock file: {}", e))?;
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write lock file: {}", e))
+}
+
+pub(crate) fn acquire_vault_lock(vault_path: &Path) -> Result<(), String> {
+    let lock_path = get_lock_file_path(vault_path);
+
+    if lock_path.exists() {
+        if let Some(lock) = read_lock(vault_path) {
+            if is_lock_live(&lock) {
+                return Err(format!(
+                    "Vault is already open in another instance (pid {})",
+                    lock.pid
+                ));
+            }
+        }
+
+        // Stale, malformed, or our own lock: reclaim it
+        fs::remove_file(&lock_path)
+            .map_err(|e| format!("Failed to remove stale lock file: {}", e))?;
+    }
+
+    write_lock(vault_path)
+}
+
+// Removes the lock only if this process owns it, so releasing never steals
+// a lock that another instance has since taken over.
+pub(crate) fn release_vault_lock(vault_path: &Path) {
+    if let Some(lock) = read_lock(vault_path) {
+        if lock.pid == std::process::id() {
+            let _ = fs::remove_file(get_lock_file_path(vault_path));
+        }
+    }
+}
+
+pub fn release_on_exit(app_handle: &tauri::AppHandle) {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    if let Ok(state_guard) = state.lock() {
+        if let Some(vault_path) = &state_guard.vault_path {
+            release_vault_lock(vault_path);
+        }
+    };
+}
+
+#[tauri::command]
+pub async fn force_unlock(app_handle: tauri::AppHandle) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    // Prefer the open vault; otherwise unlock the vault set_vault just refused
+    let vault_path = match (&state_guard.vault_path, &state_guard.blocked_vault_path) {
+        (Some(vault_path), _) => vault_path.clone(),
+        (None, Some(blocked_path)) => blocked_path.clone(),
+        (None, None) => return Err("No vault set".to_string()),
+    };
+
+    let lock_path = get_lock_file_path(&vault_path);
+    if lock_path.exists() {
+        fs::remove_file(&lock_path)
+            .map_err(|e| format!("Failed to remove lock file: {}", e))?;
+    }
+
+    if state_guard.vault_path.is_some() {
+        write_lock(&vault_path)?;
+    }
+    state_guard.blocked_vault_path = None;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    // Pid of a process that has already exited. The test binary itself
+    // exits straight away when asked for `--help`.
+    fn dead_pid() -> u32 {
+        let mut child = std::process::Command::new(std::env::current_exe().unwrap())
+            .arg("--help")
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap();
+        child.wait().unwrap();
+        child.id()
+    }
+
+    fn write_foreign_lock(vault_path: &Path, pid: u32) {
+        let lock = VaultLock { pid, timestamp: 1 };
+        fs::write(get_lock_file_path(vault_path), serde_json::to_string(&lock).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn reclaims_lock_of_dead_process() {
+        let vault = TempVault::new();
+        write_foreign_lock(vault.path(), dead_pid());
+
+        acquire_vault_lock(vault.path()).unwrap();
+
+        let lock = read_lock(vault.path()).unwrap();
+        assert_eq!(lock.pid, std::process::id());
+        assert!(lock.timestamp > 1);
+    }
+
+    #[test]
+    fn reclaims_malformed_lock() {
+        let vault = TempVault::new();
+        fs::write(get_lock_file_path(vault.path()), "garbage").unwrap();
+
+        acquire_vault_lock(vault.path()).unwrap();
+        assert_eq!(read_lock(vault.path()).unwrap().pid, std::process::id());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_lock_of_live_process() {
+        let vault = TempVault::new();
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        write_foreign_lock(vault.path(), child.id());
+
+        let result = acquire_vault_lock(vault.path());
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(read_lock(vault.path()).unwrap().pid, child.id());
+    }
+}
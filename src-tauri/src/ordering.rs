@@ -0,0 +1,329 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::fs::{relative_path_string, sanitize_relative_path, AppState, Entry};
+
+pub(crate) const ORDER_FILE_NAME: &str = ".tau_order.json";
+
+// On-disk shape of a folder's `.tau_order.json`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileOrder {
+    pub order: Vec<String>,
+}
+
+// A folder's custom order, looked up by entry name. Folders without an order
+// file (or with one that can't be parsed) get an empty index.
+#[derive(Debug, Default)]
+pub(crate) struct OrderIndex {
+    ranks: HashMap<String, usize>,
+}
+
+impl OrderIndex {
+    pub(crate) fn rank(&self, name: &str) -> Option<usize> {
+        self.ranks.get(name).copied()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.ranks.is_empty()
+    }
+
+    // Names in the order file, in their stored order.
+    pub(crate) fn names(&self) -> Vec<String> {
+        let mut names: Vec<(&String, &usize)> = self.ranks.iter().collect();
+        names.sort_by_key(|(_, rank)| **rank);
+        names.into_iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    // Ordered names come first by rank; the rest follow alphabetically,
+    // ignoring case.
+    pub(crate) fn compare(&self, a: &str, b: &str) -> Ordering {
+        match (self.rank(a), self.rank(b)) {
+            (Some(a_idx), Some(b_idx)) => a_idx.cmp(&b_idx),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.to_lowercase().cmp(&b.to_lowercase()),
+        }
+    }
+}
+
+pub(crate) fn order_file_path(dir_path: &Path) -> PathBuf {
+    dir_path.join(ORDER_FILE_NAME)
+}
+
+pub(crate) fn load(dir_path: &Path) -> OrderIndex {
+    let mut index = OrderIndex::default();
+
+    if let Ok(content) = fs::read_to_string(order_file_path(dir_path)) {
+        match serde_json::from_str::<FileOrder>(&content) {
+            Ok(file_order) => {
+                for (rank, name) in file_order.order.into_iter().enumerate() {
+                    index.ranks.insert(name, rank);
+                }
+            }
+            // Listing still works alphabetically; repair_order_file can fix it
+            Err(e) => log::warn!("Ignoring malformed order file in {}: {}", dir_path.display(), e),
+        }
+    }
+
+    index
+}
+
+pub(crate) fn save(dir_path: &Path, order: Vec<String>) -> Result<(), String> {
+    let file_order = FileOrder { order };
+    let content = serde_json::to_string_pretty(&file_order)
+        .map_err(|e| format!("Failed to serialize order: {}", e))?;
+
+    fs::write(order_file_path(dir_path), content)
+        .map_err(|e| format!("Failed to write order file: {}", e))?;
+
+    Ok(())
+}
+
+// Directories first, then files, each group following `index`.
+pub(crate) fn apply_sort(entries: &mut [Entry], index: &OrderIndex) {
+    entries.sort_by(|a, b| match (a.is_dir, b.is_dir) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => index.compare(&a.name, &b.name),
+    });
+}
+
+// Keeps a renamed entry at its place in the custom order.
+pub(crate) fn rename(dir_path: &Path, old_name: &str, new_name: &str) -> Result<(), String> {
+    let index = load(dir_path);
+    if index.rank(old_name).is_none() {
+        return Ok(());
+    }
+
+    let order = index
+        .names()
+        .into_iter()
+        .map(|name| if name == old_name { new_name.to_string() } else { name })
+        .collect();
+
+    save(dir_path, order)
+}
+
+// Drops order entries for names no longer present in `dir_path`. Folders
+// without an order file are left without one.
+pub(crate) fn prune(dir_path: &Path) -> Result<(), String> {
+    let index = load(dir_path);
+    if index.is_empty() {
+        return Ok(());
+    }
+
+    let order = index
+        .names()
+        .into_iter()
+        .filter(|name| dir_path.join(name).exists())
+        .collect();
+
+    save(dir_path, order)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderProblemKind {
+    // The file isn't valid order JSON, so the folder falls back to A-Z
+    Malformed,
+    // The file lists names that are no longer in the folder
+    MissingEntries,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrderProblem {
+    pub dir: String,
+    pub kind: OrderProblemKind,
+    // Missing names for `MissingEntries`, the parse error for `Malformed`
+    pub details: Vec<String>,
+}
+
+fn collect_order_problems(base_path: &Path, dir: &Path, problems: &mut Vec<OrderProblem>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory: {}", e))?;
+
+    let mut subdirs = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.path().is_dir() && !name.starts_with('.') {
+            subdirs.push(entry.path());
+        }
+    }
+    subdirs.sort();
+
+    if let Ok(content) = fs::read_to_string(order_file_path(dir)) {
+        let rel = relative_path_string(base_path, dir)?;
+
+        match serde_json::from_str::<FileOrder>(&content) {
+            Ok(file_order) => {
+                let missing: Vec<String> = file_order
+                    .order
+                    .into_iter()
+                    .filter(|name| !dir.join(name).exists())
+                    .collect();
+                if !missing.is_empty() {
+                    problems.push(OrderProblem { dir: rel, kind: OrderProblemKind::MissingEntries, details: missing });
+                }
+            }
+            Err(e) => problems.push(OrderProblem { dir: rel, kind: OrderProblemKind::Malformed, details: vec![e.to_string()] }),
+        }
+    }
+
+    for subdir in subdirs {
+        collect_order_problems(base_path, &subdir, problems)?;
+    }
+
+    Ok(())
+}
+
+// Order files that can't be parsed or that list entries which no longer
+// exist, for every non-hidden folder including the vault root ("").
+#[tauri::command]
+pub async fn validate_order_files(app_handle: tauri::AppHandle) -> Result<Vec<OrderProblem>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let mut problems = Vec::new();
+    collect_order_problems(base_path, base_path, &mut problems)?;
+    Ok(problems)
+}
+
+// Every JSON string value in a damaged order file, in order of appearance.
+// A hand-edited or truncated file usually still holds most of its names.
+fn recover_names(content: &str) -> Vec<String> {
+    let string_regex = Regex::new(r#"("(?:[^"\\]|\\.)*")(\s*:)?"#).unwrap();
+
+    string_regex
+        .captures_iter(content)
+        // Object keys such as "order" are followed by a colon
+        .filter(|captures| captures.get(2).is_none())
+        .filter_map(|captures| serde_json::from_str::<String>(&captures[1]).ok())
+        .collect()
+}
+
+// Rewrites `dir`'s order file keeping whatever names can still be read and
+// still exist, in their original order. Returns the repaired order.
+#[tauri::command]
+pub async fn repair_order_file(app_handle: tauri::AppHandle, dir: String) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    repair_order(base_path, &dir)
+}
+
+fn repair_order(base_path: &Path, dir: &str) -> Result<Vec<String>, String> {
+    let dir_path = base_path.join(sanitize_relative_path(dir)?);
+    let content = fs::read_to_string(order_file_path(&dir_path))
+        .map_err(|e| format!("Failed to read order file: {}", e))?;
+
+    let names = match serde_json::from_str::<FileOrder>(&content) {
+        Ok(file_order) => file_order.order,
+        Err(_) => recover_names(&content),
+    };
+
+    let mut order: Vec<String> = Vec::new();
+    for name in names {
+        if dir_path.join(&name).exists() && !order.contains(&name) {
+            order.push(name);
+        }
+    }
+
+    save(&dir_path, order.clone())?;
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    fn entry(name: &str, is_dir: bool) -> Entry {
+        Entry {
+            name: name.to_string(),
+            path: name.to_string(),
+            is_dir,
+            modified: "0".to_string(),
+            created: None,
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn save_then_load_keeps_ranks() {
+        let vault = TempVault::new();
+        save(vault.path(), vec!["b.md".to_string(), "a.md".to_string(), "c.md".to_string()]).unwrap();
+
+        let index = load(vault.path());
+        assert_eq!(index.rank("b.md"), Some(0));
+        assert_eq!(index.rank("a.md"), Some(1));
+        assert_eq!(index.rank("c.md"), Some(2));
+        assert_eq!(index.rank("missing.md"), None);
+        assert_eq!(index.names(), vec!["b.md", "a.md", "c.md"]);
+    }
+
+    #[test]
+    fn missing_or_malformed_file_gives_empty_index() {
+        let vault = TempVault::new();
+        assert!(load(vault.path()).is_empty());
+
+        vault.write(ORDER_FILE_NAME, "{ not json");
+        assert!(load(vault.path()).is_empty());
+    }
+
+    #[test]
+    fn apply_sort_with_partial_order() {
+        let vault = TempVault::new();
+        save(vault.path(), vec!["zeta.md".to_string(), "Notes".to_string(), "beta.md".to_string()]).unwrap();
+
+        let mut entries = vec![
+            entry("alpha.md", false),
+            entry("beta.md", false),
+            entry("Archive", true),
+            entry("Charlie.md", false),
+            entry("zeta.md", false),
+            entry("Notes", true),
+        ];
+        apply_sort(&mut entries, &load(vault.path()));
+
+        let names: Vec<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+        // Folders first; ranked names lead each group, the rest follow A-Z
+        assert_eq!(names, vec!["Notes", "Archive", "zeta.md", "beta.md", "alpha.md", "Charlie.md"]);
+    }
+
+    #[test]
+    fn truncated_order_file_is_reported_and_recovered() {
+        let vault = TempVault::new();
+        vault.write("Notes/a.md", "");
+        vault.write("Notes/b.md", "");
+        vault.write("Notes/.tau_order.json", r#"{"order":["b.md","gone.md","a.md","#);
+
+        let mut problems = Vec::new();
+        collect_order_problems(vault.path(), vault.path(), &mut problems).unwrap();
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].dir, "Notes");
+        assert!(matches!(problems[0].kind, OrderProblemKind::Malformed));
+
+        assert_eq!(repair_order(vault.path(), "Notes").unwrap(), vec!["b.md", "a.md"]);
+        assert_eq!(load(&vault.path().join("Notes")).names(), vec!["b.md", "a.md"]);
+
+        problems.clear();
+        collect_order_problems(vault.path(), vault.path(), &mut problems).unwrap();
+        assert!(problems.is_empty());
+    }
+}
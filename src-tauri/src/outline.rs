@@ -0,0 +1,494 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+use tauri::Manager;
+
+use crate::frontmatter::{is_note_readonly, readonly_error, split_frontmatter};
+use crate::fs::{collect_markdown_files, parse_links_from_content, relative_path_string, with_note_content, write_atomic, AppState, Link};
+use crate::links::NoteIndex;
+
+pub const OUTLINE_UPDATED_EVENT: &str = "outline://updated";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Heading {
+    pub level: usize,
+    pub text: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HeadingRef {
+    pub path: String,
+    pub level: usize,
+    pub text: String,
+    pub line: usize,
+}
+
+// ATX headings (`# Title` .. `###### Title`) with 1-based line numbers.
+// Frontmatter and fenced code blocks are skipped.
+pub(crate) fn parse_headings(content: &str) -> Vec<Heading> {
+    let mut headings = Vec::new();
+    let mut in_fence = false;
+    let mut in_frontmatter = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+
+        if index == 0 && line.trim_end() == "---" {
+            in_frontmatter = true;
+            continue;
+        }
+        if in_frontmatter {
+            if matches!(line.trim_end(), "---" | "...") {
+                in_frontmatter = false;
+            }
+            continue;
+        }
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || line.len() - trimmed.len() > 3 {
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if level == 0 || level > 6 {
+            continue;
+        }
+
+        let rest = &trimmed[level..];
+        if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+            continue;
+        }
+
+        // Optional closing sequence: `## Title ##`
+        let text = rest.trim();
+        let text = match text.trim_end_matches('#') {
+            stripped if stripped.is_empty() || stripped.ends_with([' ', '\t']) => stripped.trim_end(),
+            _ => text,
+        };
+
+        if !text.is_empty() {
+            headings.push(Heading { level, text: text.to_string(), line: index + 1 });
+        }
+    }
+
+    headings
+}
+
+// GitHub-style heading anchor: lowercase, punctuation dropped, spaces to `-`.
+pub(crate) fn anchor_slug(title: &str) -> String {
+    title
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+// Outline of a note as of its last write through the app. `modified` lets
+// readers notice edits made elsewhere and parse afresh.
+pub struct CachedOutline {
+    pub(crate) modified: Option<SystemTime>,
+    pub(crate) headings: Vec<Heading>,
+}
+
+// Cache keys use forward slashes so `a\b.md` and `a/b.md` are one note.
+fn outline_key(rel: &str) -> String {
+    rel.replace('\\', "/")
+}
+
+// Caches the outline of `content`, which was just written to `file_path`.
+pub(crate) fn cache_outline(outlines: &mut HashMap<String, CachedOutline>, rel: &str, file_path: &Path, content: &str) {
+    let modified = fs::metadata(file_path).and_then(|m| m.modified()).ok();
+    outlines.insert(outline_key(rel), CachedOutline { modified, headings: parse_headings(content) });
+}
+
+// Cached headings of `rel`, as long as they were taken at mtime `modified`.
+fn cached_headings(outlines: &HashMap<String, CachedOutline>, rel: &str, modified: Option<SystemTime>) -> Option<Vec<Heading>> {
+    let cached = outlines.get(&outline_key(rel))?;
+    (cached.modified.is_some() && cached.modified == modified).then(|| cached.headings.clone())
+}
+
+// Drops cached outlines for `rel` and, if it's a folder, everything in it.
+pub(crate) fn forget_outlines(outlines: &mut HashMap<String, CachedOutline>, rel: &str) {
+    let rel = outline_key(rel);
+    let prefix = format!("{}/", rel.trim_end_matches('/'));
+    outlines.retain(|path, _| path != &rel && !path.starts_with(&prefix));
+}
+
+pub(crate) fn collect_vault_headings(base_path: &Path) -> Result<Vec<HeadingRef>, String> {
+    let mut headings = Vec::new();
+
+    for path in collect_markdown_files(base_path)? {
+        let rel = relative_path_string(base_path, &path)?;
+        let note_headings = with_note_content(&path, parse_headings)?;
+
+        headings.extend(note_headings.into_iter().map(|heading| HeadingRef {
+            path: rel.clone(),
+            level: heading.level,
+            text: heading.text,
+            line: heading.line,
+        }));
+    }
+
+    Ok(headings)
+}
+
+#[tauri::command]
+pub async fn get_all_headings(app_handle: tauri::AppHandle) -> Result<Vec<HeadingRef>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    collect_vault_headings(base_path)
+}
+
+// Headings of one note, from the cache when it is still current.
+#[tauri::command]
+pub async fn get_outline(app_handle: tauri::AppHandle, rel: String) -> Result<Vec<Heading>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path.clone(),
+        None => return Err("No vault set".to_string()),
+    };
+
+    let file_path = base_path.join(&rel);
+
+    if !file_path.is_file() {
+        forget_outlines(&mut state_guard.outlines, &rel);
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    let modified = fs::metadata(&file_path).and_then(|m| m.modified()).ok();
+    if let Some(headings) = cached_headings(&state_guard.outlines, &rel, modified) {
+        return Ok(headings);
+    }
+
+    let headings = with_note_content(&file_path, parse_headings)?;
+    state_guard.outlines.insert(outline_key(&rel), CachedOutline { modified, headings: headings.clone() });
+
+    Ok(headings)
+}
+
+// Headings of the note `target` resolves to that start with `prefix`
+// (case-insensitive), in document order. Unresolved targets give no
+// suggestions rather than an error, since the link may still be half-typed.
+#[tauri::command]
+pub async fn suggest_headings(app_handle: tauri::AppHandle, target: String, prefix: String) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    heading_suggestions(base_path, &target, &prefix)
+}
+
+fn heading_suggestions(base_path: &Path, target: &str, prefix: &str) -> Result<Vec<String>, String> {
+    let note = match NoteIndex::build(base_path)?.resolve_link_target("", target) {
+        Some(note) => note,
+        None => return Ok(Vec::new()),
+    };
+
+    let prefix = prefix.trim().to_lowercase();
+    let mut suggestions: Vec<String> = Vec::new();
+
+    for heading in with_note_content(&base_path.join(&note), parse_headings)? {
+        if heading.text.to_lowercase().starts_with(&prefix) && !suggestions.contains(&heading.text) {
+            suggestions.push(heading.text);
+        }
+    }
+
+    Ok(suggestions)
+}
+
+// Byte range of the section under the first heading matching `heading`
+// (by text, case-insensitively, or by anchor slug): from the line after it
+// to the next heading of the same or a higher level, or the end of the note.
+pub(crate) fn section_range(content: &str, heading: &str) -> Option<(usize, usize)> {
+    let headings = parse_headings(content);
+    let wanted = heading.trim().trim_start_matches('#').trim();
+    let wanted_lower = wanted.to_lowercase();
+
+    let index = headings
+        .iter()
+        .position(|h| h.text.to_lowercase() == wanted_lower || anchor_slug(&h.text) == wanted_lower)?;
+    let level = headings[index].level;
+    let start_line = headings[index].line + 1;
+    let end_line = headings[index + 1..]
+        .iter()
+        .find(|h| h.level <= level)
+        .map(|h| h.line);
+
+    // Byte offset at which each 1-based line starts
+    let mut line_starts = vec![0];
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        offset += line.len();
+        line_starts.push(offset);
+    }
+
+    let line_start = |line: usize| line_starts.get(line - 1).copied().unwrap_or(content.len());
+    let start = line_start(start_line);
+    let end = end_line.map(line_start).unwrap_or(content.len());
+
+    Some((start, end.max(start)))
+}
+
+#[tauri::command]
+pub async fn links_under_heading(app_handle: tauri::AppHandle, rel: String, heading: String) -> Result<Vec<Link>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    section_links(base_path, &rel, &heading)
+}
+
+fn section_links(base_path: &Path, rel: &str, heading: &str) -> Result<Vec<Link>, String> {
+    let file_path = base_path.join(rel);
+
+    if !file_path.is_file() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    with_note_content(&file_path, |content| {
+        let (start, end) = section_range(content, heading)
+            .ok_or_else(|| format!("Heading '{}' not found in '{}'", heading, rel))?;
+
+        Ok(parse_links_from_content(content, rel)
+            .into_iter()
+            .filter(|link| link.position >= start && link.position + link.length <= end)
+            .collect())
+    })?
+}
+
+const TOC_START: &str = "<!-- toc -->";
+const TOC_END: &str = "<!-- /toc -->";
+
+// Nested list of links to every heading, indented relative to the
+// shallowest level. Repeated titles get `-1`, `-2`, ... like GitHub does.
+fn build_toc(headings: &[Heading]) -> String {
+    let min_level = headings.iter().map(|heading| heading.level).min().unwrap_or(1);
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut toc = String::new();
+
+    for heading in headings {
+        let slug = anchor_slug(&heading.text);
+        let count = seen.entry(slug.clone()).or_insert(0);
+        let anchor = if *count == 0 { slug } else { format!("{}-{}", slug, count) };
+        *count += 1;
+
+        let indent = "  ".repeat(heading.level - min_level);
+        toc.push_str(&format!("{}- [{}](#{})\n", indent, heading.text, anchor));
+    }
+
+    toc
+}
+
+// Puts the TOC block between the markers, replacing an existing one. A lone
+// start marker is expanded in place; without markers the block goes at the
+// top of the body, after any frontmatter.
+fn with_toc(content: &str, toc: &str) -> String {
+    let block = format!("{}\n{}{}\n", TOC_START, toc, TOC_END);
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let is_marker = |line: &str, marker: &str| line.trim() == marker;
+
+    if let Some(start) = lines.iter().position(|line| is_marker(line, TOC_START)) {
+        let end = lines[start..]
+            .iter()
+            .position(|line| is_marker(line, TOC_END))
+            .map(|offset| start + offset)
+            .unwrap_or(start);
+
+        let mut output: String = lines[..start].concat();
+        output.push_str(&block);
+        output.push_str(&lines[end + 1..].concat());
+        return output;
+    }
+
+    let body_start = match split_frontmatter(content) {
+        Some((_, body)) => content.len() - body.len(),
+        None => 0,
+    };
+
+    format!("{}{}\n{}", &content[..body_start], block, &content[body_start..])
+}
+
+#[tauri::command]
+pub async fn insert_toc(app_handle: tauri::AppHandle, rel: String) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let file_path = base_path.join(&rel);
+
+    if !file_path.is_file() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    if is_note_readonly(&file_path) {
+        return Err(readonly_error(&rel));
+    }
+
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read note: {}", e))?;
+
+    let toc = build_toc(&parse_headings(&content));
+    let updated = with_toc(&content, &toc);
+
+    write_atomic(&file_path, updated.as_bytes())
+        .map_err(|e| format!("Failed to write note: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    fn texts(headings: &[Heading]) -> Vec<&str> {
+        headings.iter().map(|heading| heading.text.as_str()).collect()
+    }
+
+    #[test]
+    fn writing_headings_updates_cached_outline() {
+        let vault = TempVault::new();
+        let mut outlines = HashMap::new();
+
+        let content = "# One\ntext";
+        let path = vault.write("Notes/a.md", content);
+        cache_outline(&mut outlines, "Notes/a.md", &path, content);
+
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        assert_eq!(texts(&cached_headings(&outlines, "Notes/a.md", modified).unwrap()), vec!["One"]);
+
+        let content = "# One\n## Two\n### Three";
+        vault.write("Notes/a.md", content);
+        cache_outline(&mut outlines, "Notes/a.md", &path, content);
+
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        assert_eq!(texts(&cached_headings(&outlines, "Notes/a.md", modified).unwrap()), vec!["One", "Two", "Three"]);
+        assert_eq!(outlines.len(), 1);
+    }
+
+    #[test]
+    fn edits_made_elsewhere_miss_the_cache() {
+        let vault = TempVault::new();
+        let mut outlines = HashMap::new();
+        let path = vault.write("a.md", "# Old");
+        cache_outline(&mut outlines, "a.md", &path, "# Old");
+
+        let later = SystemTime::now() + std::time::Duration::from_secs(60);
+        assert!(cached_headings(&outlines, "a.md", Some(later)).is_none());
+        assert!(cached_headings(&outlines, "a.md", None).is_none());
+    }
+
+    #[test]
+    fn keys_are_normalized_for_lookup_and_forgetting() {
+        let vault = TempVault::new();
+        let mut outlines = HashMap::new();
+        for rel in ["Folder\\a.md", "Folder/Sub/b.md", "Folder2/c.md"] {
+            let path = vault.write(&rel.replace('\\', "/"), "# H");
+            cache_outline(&mut outlines, rel, &path, "# H");
+        }
+
+        assert!(outlines.contains_key("Folder/a.md"));
+
+        forget_outlines(&mut outlines, "Folder\\Sub");
+        assert!(!outlines.contains_key("Folder/Sub/b.md"));
+
+        forget_outlines(&mut outlines, "Folder");
+        let left: Vec<&String> = outlines.keys().collect();
+        assert_eq!(left, vec!["Folder2/c.md"]);
+    }
+
+    #[test]
+    fn rerunning_toc_replaces_the_block() {
+        let run = |content: &str| with_toc(content, &build_toc(&parse_headings(content)));
+
+        let first = run("# Title\n\n## A\ntext\n");
+        assert_eq!(first, "<!-- toc -->\n- [Title](#title)\n  - [A](#a)\n<!-- /toc -->\n\n# Title\n\n## A\ntext\n");
+
+        let second = run(&format!("{}## B\n", first));
+        assert_eq!(second.matches(TOC_START).count(), 1);
+        assert_eq!(
+            second,
+            "<!-- toc -->\n- [Title](#title)\n  - [A](#a)\n  - [B](#b)\n<!-- /toc -->\n\n# Title\n\n## A\ntext\n## B\n"
+        );
+        assert_eq!(run(&second), second);
+    }
+
+    #[test]
+    fn headings_inside_code_fences_are_skipped() {
+        let vault = TempVault::new();
+        vault.write("Guide.md", "---\ntitle: # not a heading\n---\n# Setup\n```sh\n# install it\n```\n~~~\n## Also code\n~~~\n## Usage");
+
+        let headings = collect_vault_headings(vault.path()).unwrap();
+        let found: Vec<(&str, usize, &str, usize)> = headings
+            .iter()
+            .map(|h| (h.path.as_str(), h.level, h.text.as_str(), h.line))
+            .collect();
+
+        assert_eq!(found, vec![("Guide.md", 1, "Setup", 4), ("Guide.md", 2, "Usage", 11)]);
+    }
+
+    #[test]
+    fn heading_suggestions_come_from_the_resolved_note_only() {
+        let vault = TempVault::new();
+        vault.write("Projects/Roadmap.md", "# Goals\n## Goals\n## Timeline\n### Go-live");
+        vault.write("Other.md", "# Goals elsewhere\n## Gotchas");
+
+        let suggest = |target: &str, prefix: &str| heading_suggestions(vault.path(), target, prefix).unwrap();
+
+        assert_eq!(suggest("Roadmap", "go"), vec!["Goals", "Go-live"]);
+        assert_eq!(suggest("Roadmap", ""), vec!["Goals", "Timeline", "Go-live"]);
+        assert!(suggest("Missing", "go").is_empty());
+    }
+
+    #[test]
+    fn links_under_another_heading_are_excluded() {
+        let vault = TempVault::new();
+        vault.write(
+            "Note.md",
+            "[[Intro link]]\n# Reading\n[[Book]]\n## Articles\n[[Article]]\n# Watching\n[[Film]]",
+        );
+
+        let targets = |heading: &str| -> Vec<String> {
+            section_links(vault.path(), "Note.md", heading)
+                .unwrap()
+                .into_iter()
+                .map(|link| link.target_note)
+                .collect()
+        };
+
+        // A section runs through its subheadings up to the next same-level heading
+        assert_eq!(targets("Reading"), vec!["Book", "Article"]);
+        assert_eq!(targets("articles"), vec!["Article"]);
+        assert_eq!(targets("Watching"), vec!["Film"]);
+        assert!(section_links(vault.path(), "Note.md", "Listening").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use pulldown_cmark::{Event, Options, Parser, TagEnd};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use tauri::Manager;
+
+use crate::config::read_vault_config;
+use crate::frontmatter::split_frontmatter;
+use crate::fs::{collect_markdown_files, with_note_content, AppState};
+
+// Common English words left out of `word_frequency` unless the vault config
+// sets its own list.
+const DEFAULT_STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be", "been", "but", "by",
+    "can", "could", "did", "do", "does", "for", "from", "had", "has", "have", "he", "her", "his", "how",
+    "i", "if", "in", "into", "is", "it", "its", "just", "me", "more", "my", "no", "not", "of", "on", "one",
+    "or", "our", "out", "she", "so", "some", "than", "that", "the", "their", "them", "then", "there",
+    "these", "they", "this", "to", "up", "was", "we", "were", "what", "when", "which", "who", "will",
+    "with", "would", "you", "your",
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WordFreq {
+    pub word: String,
+    pub count: usize,
+}
+
+// Wikilinks aren't CommonMark, so they're swapped for their display text
+// (or target) before parsing. Embeds lose the `!` the same way.
+fn replace_wikilinks(markdown: &str) -> String {
+    let wiki_regex = Regex::new(r"!?\[\[([^\[\]]+)\]\]").unwrap();
+
+    wiki_regex
+        .replace_all(markdown, |captures: &regex::Captures| {
+            let inner = &captures[1];
+            match inner.split_once('|') {
+                Some((_, display)) => display.trim().to_string(),
+                None => inner.trim().to_string(),
+            }
+        })
+        .to_string()
+}
+
+// Ends the current line, or the current block with a blank line, without
+// stacking up empty lines.
+fn break_line(output: &mut String, blank: bool) {
+    let trimmed_len = output.trim_end_matches([' ', '\t']).len();
+    output.truncate(trimmed_len);
+
+    if output.is_empty() {
+        return;
+    }
+
+    let wanted = if blank { "\n\n" } else { "\n" };
+    while !output.ends_with(wanted) {
+        output.push('\n');
+    }
+}
+
+pub(crate) fn markdown_to_plaintext(content: &str) -> String {
+    let body = split_frontmatter(content).map(|(_, body)| body).unwrap_or(content);
+    let markdown = replace_wikilinks(body);
+
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut output = String::with_capacity(markdown.len());
+
+    for event in Parser::new_ext(&markdown, options) {
+        match event {
+            Event::Text(text) | Event::Code(text) => output.push_str(&text),
+            Event::SoftBreak => output.push(' '),
+            Event::HardBreak => output.push('\n'),
+            Event::End(TagEnd::Paragraph)
+            | Event::End(TagEnd::Heading(_))
+            | Event::End(TagEnd::CodeBlock)
+            | Event::End(TagEnd::BlockQuote(_))
+            | Event::End(TagEnd::List(_))
+            | Event::End(TagEnd::Table) => break_line(&mut output, true),
+            Event::End(TagEnd::Item) | Event::End(TagEnd::TableHead) | Event::End(TagEnd::TableRow) => {
+                break_line(&mut output, false)
+            }
+            Event::End(TagEnd::TableCell) => output.push('\t'),
+            _ => {}
+        }
+    }
+
+    output.trim().to_string()
+}
+
+#[tauri::command]
+pub async fn note_to_plaintext(app_handle: tauri::AppHandle, rel: String) -> Result<String, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let file_path = base_path.join(&rel);
+
+    if !file_path.exists() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read note: {}", e))?;
+
+    Ok(markdown_to_plaintext(&content))
+}
+
+// Lowercased words of `text` with punctuation stripped. Apostrophes inside a
+// word are kept ("don't"); tokens that are only digits aren't words.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| !word.is_empty() && !word.chars().all(|c| c.is_ascii_digit()))
+        .map(|word| word.to_lowercase())
+}
+
+// The most common words across every note body, frontmatter and markdown
+// syntax excluded, most frequent first.
+#[tauri::command]
+pub async fn word_frequency(app_handle: tauri::AppHandle, limit: usize) -> Result<Vec<WordFreq>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    count_words(base_path, limit)
+}
+
+fn count_words(base_path: &Path, limit: usize) -> Result<Vec<WordFreq>, String> {
+    let stopwords: HashSet<String> = match read_vault_config(base_path).stopwords {
+        Some(stopwords) => stopwords.iter().map(|word| word.trim().to_lowercase()).collect(),
+        None => DEFAULT_STOPWORDS.iter().map(|word| word.to_string()).collect(),
+    };
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for path in collect_markdown_files(base_path)? {
+        let text = with_note_content(&path, markdown_to_plaintext)?;
+        for word in words(&text) {
+            if !stopwords.contains(&word) {
+                *counts.entry(word).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut frequencies: Vec<WordFreq> = counts
+        .into_iter()
+        .map(|(word, count)| WordFreq { word, count })
+        .collect();
+    frequencies.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+    frequencies.truncate(limit);
+
+    Ok(frequencies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn wikilinks_keep_their_display_text_and_headings_lose_markers() {
+        let content = "---\ntitle: Draft\n---\n# Heading\n\nSee [[Foo|Bar]] and [[Baz]], **bold** and ![[pic.png]].\n\n- one\n- two";
+
+        assert_eq!(markdown_to_plaintext(content), "Heading\n\nSee Bar and Baz, bold and pic.png.\n\none\ntwo");
+    }
+
+    #[test]
+    fn word_counts_skip_stopwords_and_add_up_across_notes() {
+        let vault = TempVault::new();
+        vault.write("One.md", "---\ntags: [garden]\n---\nThe garden and the Garden's gate.");
+        vault.write("Two.md", "# Garden\n\nA gate, a garden, 2024.");
+
+        let counts = count_words(vault.path(), 10).unwrap();
+        let pairs: Vec<(&str, usize)> = counts.iter().map(|freq| (freq.word.as_str(), freq.count)).collect();
+        assert_eq!(pairs, vec![("garden", 3), ("gate", 2), ("garden's", 1)]);
+
+        // A configured list replaces the default one
+        vault.write(".tau_config.json", r#"{"stopwords":["Garden"]}"#);
+        let counts = count_words(vault.path(), 1).unwrap();
+        assert_eq!((counts[0].word.as_str(), counts[0].count), ("a", 2));
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::fs::{collect_markdown_files, is_markdown_file, relative_path_string, write_atomic, AppState};
+
+// Old note paths mapped to where the note was renamed, so links that weren't
+// rewritten still find it.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct RedirectMap {
+    pub redirects: BTreeMap<String, String>,
+}
+
+fn get_redirects_path(base_path: &Path) -> PathBuf {
+    base_path.join(".tau_redirects.json")
+}
+
+pub(crate) fn read_redirects(base_path: &Path) -> RedirectMap {
+    fs::read_to_string(get_redirects_path(base_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_redirects(base_path: &Path, redirect_map: &RedirectMap) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(redirect_map)
+        .map_err(|e| format!("Failed to serialize redirects: {}", e))?;
+
+    write_atomic(&get_redirects_path(base_path), content.as_bytes())
+        .map_err(|e| format!("Failed to write redirects: {}", e))
+}
+
+// Records a rename of `from` to `to`, called after the move. A renamed folder
+// records one redirect per note inside it.
+pub(crate) fn record_rename(base_path: &Path, from: &str, to: &str) -> Result<(), String> {
+    let (from, to) = (from.replace('\\', "/"), to.replace('\\', "/"));
+    let to_path = base_path.join(&to);
+
+    let moved: Vec<(String, String)> = if to_path.is_dir() {
+        collect_markdown_files(&to_path)?
+            .iter()
+            .map(|note| {
+                let new_rel = relative_path_string(base_path, note)?.replace('\\', "/");
+                let inner = new_rel.strip_prefix(&to).unwrap_or(&new_rel);
+                Ok((format!("{}{}", from, inner), new_rel.clone()))
+            })
+            .collect::<Result<_, String>>()?
+    } else if is_markdown_file(&to_path) {
+        vec![(from, to)]
+    } else {
+        Vec::new()
+    };
+
+    if moved.is_empty() {
+        return Ok(());
+    }
+
+    let mut redirect_map = read_redirects(base_path);
+    for (old, new) in moved {
+        // A note moved back to an old path no longer needs that redirect, and
+        // case-only renames still resolve without one
+        redirect_map.redirects.remove(&new);
+        if old.to_lowercase() != new.to_lowercase() {
+            redirect_map.redirects.insert(old, new);
+        }
+    }
+
+    write_redirects(base_path, &redirect_map)
+}
+
+#[tauri::command]
+pub async fn list_redirects(app_handle: tauri::AppHandle) -> Result<BTreeMap<String, String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    Ok(read_redirects(base_path).redirects)
+}
+
+#[tauri::command]
+pub async fn clear_redirect(app_handle: tauri::AppHandle, old: String) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let mut redirect_map = read_redirects(base_path);
+    if redirect_map.redirects.remove(&old.replace('\\', "/")).is_none() {
+        return Err(format!("No redirect recorded for '{}'", old));
+    }
+
+    write_redirects(base_path, &redirect_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::links::NoteIndex;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn stale_link_resolves_through_redirect_chain() {
+        let vault = TempVault::new();
+        vault.write("C.md", "# C");
+        // A.md was renamed to B.md, and later B.md to C.md
+        record_rename(vault.path(), "A.md", "B.md").unwrap();
+        record_rename(vault.path(), "B.md", "C.md").unwrap();
+
+        let index = NoteIndex::build(vault.path()).unwrap();
+        assert_eq!(index.resolve_link_target("", "A").as_deref(), Some("C.md"));
+        assert_eq!(index.resolve_link_target("", "B").as_deref(), Some("C.md"));
+    }
+
+    #[test]
+    fn redirect_cycles_do_not_resolve() {
+        let vault = TempVault::new();
+        let redirect_map = RedirectMap {
+            redirects: [("A.md", "B.md"), ("B.md", "A.md")]
+                .into_iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
+        };
+        write_redirects(vault.path(), &redirect_map).unwrap();
+
+        assert_eq!(NoteIndex::build(vault.path()).unwrap().resolve_link_target("", "A"), None);
+    }
+}
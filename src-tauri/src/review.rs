@@ -0,0 +1,119 @@
+use chrono::{DateTime, Days, Local, NaiveDate};
+use std::path::Path;
+use tauri::Manager;
+
+use crate::frontmatter::{is_note_readonly, parse_frontmatter, readonly_error, split_frontmatter, with_frontmatter, Frontmatter};
+use crate::fs::{collect_markdown_files, relative_path_string, with_note_content, write_atomic, AppState};
+
+// Frontmatter key holding the next review date as `YYYY-MM-DD`.
+const REVIEW_DATE_KEY: &str = "review_date";
+
+// The note's review date. A full timestamp counts by its date; a missing or
+// unreadable value means the note isn't scheduled.
+fn review_date(content: &str) -> Option<NaiveDate> {
+    let frontmatter = parse_frontmatter(content)?;
+    let value = frontmatter.get(REVIEW_DATE_KEY)?.as_str()?.trim();
+
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .or_else(|| DateTime::parse_from_rfc3339(value).ok().map(|time| time.date_naive()))
+}
+
+// Notes due on or before `today`, the longest overdue first.
+fn due_reviews(base_path: &Path, today: NaiveDate) -> Result<Vec<String>, String> {
+    let mut due = Vec::new();
+
+    for path in collect_markdown_files(base_path)? {
+        if let Some(date) = with_note_content(&path, review_date)? {
+            if date <= today {
+                due.push((date, relative_path_string(base_path, &path)?));
+            }
+        }
+    }
+
+    due.sort();
+    Ok(due.into_iter().map(|(_, rel)| rel).collect())
+}
+
+#[tauri::command]
+pub async fn get_due_reviews(app_handle: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    due_reviews(base_path, Local::now().date_naive())
+}
+
+// Schedules the next review `next_interval_days` from today, adding a
+// frontmatter block if the note has none.
+#[tauri::command]
+pub async fn mark_reviewed(app_handle: tauri::AppHandle, rel: String, next_interval_days: u64) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    schedule_review(base_path, &rel, next_interval_days, Local::now().date_naive())
+}
+
+fn schedule_review(base_path: &Path, rel: &str, next_interval_days: u64, today: NaiveDate) -> Result<(), String> {
+    let file_path = base_path.join(rel);
+
+    if !file_path.is_file() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    if is_note_readonly(&file_path) {
+        return Err(readonly_error(rel));
+    }
+
+    // Read the same lenient way `due_reviews` does, so a note it lists can
+    // always be marked
+    let content = with_note_content(&file_path, |content| content.to_string())?;
+
+    // Rewriting unparseable frontmatter would throw the user's data away
+    let mut frontmatter = match (split_frontmatter(&content), parse_frontmatter(&content)) {
+        (_, Some(frontmatter)) => frontmatter,
+        (None, None) => Frontmatter::new(),
+        (Some(_), None) => return Err(format!("Frontmatter of '{}' is not valid YAML", rel)),
+    };
+
+    let next = today
+        .checked_add_days(Days::new(next_interval_days))
+        .ok_or_else(|| format!("Interval of {} days is too long", next_interval_days))?;
+
+    frontmatter.insert(REVIEW_DATE_KEY.into(), next.format("%Y-%m-%d").to_string().into());
+
+    let updated = with_frontmatter(&content, &frontmatter)?;
+    write_atomic(&file_path, updated.as_bytes())
+        .map_err(|e| format!("Failed to write note: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn marking_a_due_note_pushes_it_out() {
+        let vault = TempVault::new();
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        vault.write("Yesterday.md", "---\nreview_date: 2024-03-09\n---\nbody");
+        vault.write("Today.md", "---\nreview_date: 2024-03-10T08:00:00+00:00\n---\n");
+        vault.write("Tomorrow.md", "---\nreview_date: 2024-03-11\n---\n");
+        vault.write("Unscheduled.md", "no frontmatter");
+
+        assert_eq!(due_reviews(vault.path(), today).unwrap(), vec!["Yesterday.md", "Today.md"]);
+
+        schedule_review(vault.path(), "Yesterday.md", 3, today).unwrap();
+        assert_eq!(vault.read("Yesterday.md"), "---\nreview_date: 2024-03-13\n---\nbody");
+        assert_eq!(due_reviews(vault.path(), today).unwrap(), vec!["Today.md"]);
+    }
+}
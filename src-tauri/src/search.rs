@@ -0,0 +1,456 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::fs::{collect_markdown_files, relative_path_string, sanitize_relative_path, with_note_content, AppState};
+use crate::timing::timed;
+
+const MAX_PATTERN_LENGTH: usize = 1000;
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchHit {
+    pub path: String,
+    pub line: usize,
+    pub text: String,
+    // Byte offsets of the first match within `text`
+    pub match_start: usize,
+    pub match_end: usize,
+    // Byte ranges of every non-overlapping match on the line
+    pub matches: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExtractHit {
+    pub path: String,
+    pub line: usize,
+    // Capture groups 1.. in order, "" for groups that didn't participate.
+    // A pattern without groups yields the whole match.
+    pub groups: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TermCount {
+    pub path: String,
+    pub count: usize,
+}
+
+// Resolves the optional search scope to a directory inside the vault.
+fn resolve_scope(base_path: &Path, scope: Option<String>) -> Result<PathBuf, String> {
+    let scope = match scope {
+        Some(scope) => scope,
+        None => return Ok(base_path.to_path_buf()),
+    };
+
+    let scope_path = base_path.join(sanitize_relative_path(&scope)?);
+
+    if !scope_path.exists() {
+        return Err(format!("Scope '{}' does not exist", scope));
+    }
+
+    if !scope_path.is_dir() {
+        return Err(format!("Scope '{}' is not a directory", scope));
+    }
+
+    Ok(scope_path)
+}
+
+// Case-insensitive matching that reports byte ranges in the original line.
+// Lowercasing can change a character's byte length, so each byte of the
+// lowercased line remembers which original character it came from; ranges
+// always start and end on char boundaries of `line`.
+fn find_matches(line: &str, query_lower: &str) -> Vec<(usize, usize)> {
+    let mut lower = String::with_capacity(line.len());
+    let mut origin: Vec<(usize, usize)> = Vec::with_capacity(line.len());
+
+    for (start, c) in line.char_indices() {
+        let end = start + c.len_utf8();
+        for lc in c.to_lowercase() {
+            lower.push(lc);
+            origin.extend(std::iter::repeat((start, end)).take(lc.len_utf8()));
+        }
+    }
+
+    lower
+        .match_indices(query_lower)
+        .map(|(index, matched)| (origin[index].0, origin[index + matched.len() - 1].1))
+        .collect()
+}
+
+// Walks the markdown files under `scope_path`, reporting every line for
+// which `find` returns at least one match range. Notes that aren't valid
+// UTF-8 are searched with the bad bytes replaced.
+fn search_files<F>(base_path: &Path, scope_path: &Path, find: F) -> Result<Vec<SearchHit>, String>
+where
+    F: Fn(&str) -> Vec<(usize, usize)>,
+{
+    let mut hits = Vec::new();
+
+    for path in collect_markdown_files(scope_path)? {
+        let relative_path = relative_path_string(base_path, &path)?;
+
+        let file_hits = with_note_content(&path, |content| {
+            let mut file_hits = Vec::new();
+
+            for (index, line) in content.lines().enumerate() {
+                let matches = find(line);
+
+                if let Some(&(match_start, match_end)) = matches.first() {
+                    file_hits.push(SearchHit {
+                        path: relative_path.clone(),
+                        line: index + 1,
+                        text: line.to_string(),
+                        match_start,
+                        match_end,
+                        matches,
+                    });
+                }
+            }
+
+            file_hits
+        })?;
+
+        hits.extend(file_hits);
+    }
+
+    Ok(hits)
+}
+
+#[tauri::command]
+pub async fn search_notes(app_handle: tauri::AppHandle, query: String, scope: Option<String>) -> Result<Vec<SearchHit>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let scope_path = resolve_scope(base_path, scope)?;
+    let query_lower = query.to_lowercase();
+
+    if query_lower.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    timed!("search_notes", scope_path.display(), {
+        search_files(base_path, &scope_path, |line| find_matches(line, &query_lower))
+    })
+}
+
+// Compiles a user-supplied pattern. The regex engine runs in linear time,
+// so the remaining risk is patterns whose compiled form is huge; those are
+// capped instead of being allowed to eat memory.
+pub(crate) fn compile_search_pattern(pattern: &str) -> Result<Regex, String> {
+    if pattern.is_empty() {
+        return Err("Pattern must not be empty".to_string());
+    }
+
+    if pattern.len() > MAX_PATTERN_LENGTH {
+        return Err(format!("Pattern is too long (max {} characters)", MAX_PATTERN_LENGTH));
+    }
+
+    RegexBuilder::new(pattern)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .dfa_size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| format!("Invalid pattern: {}", e))
+}
+
+// Byte ranges of `regex` on one line. Empty matches (e.g. a lone `^`) have
+// nothing to highlight and are skipped.
+fn regex_matches(regex: &Regex, line: &str) -> Vec<(usize, usize)> {
+    regex
+        .find_iter(line)
+        .filter(|m| !m.is_empty())
+        .map(|m| (m.start(), m.end()))
+        .collect()
+}
+
+#[tauri::command]
+pub async fn regex_search(app_handle: tauri::AppHandle, pattern: String, scope: Option<String>) -> Result<Vec<SearchHit>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let scope_path = resolve_scope(base_path, scope)?;
+    let regex = compile_search_pattern(&pattern)?;
+
+    timed!("regex_search", scope_path.display(), {
+        search_files(base_path, &scope_path, |line| regex_matches(&regex, line))
+    })
+}
+
+// Pulls capture groups out of every match, line by line, so structured
+// values such as `Amount: \$(\d+)` can be collected across notes.
+fn extract_from_files(base_path: &Path, scope_path: &Path, regex: &Regex) -> Result<Vec<ExtractHit>, String> {
+    let mut hits = Vec::new();
+
+    for path in collect_markdown_files(scope_path)? {
+        let relative_path = relative_path_string(base_path, &path)?;
+
+        let file_hits = with_note_content(&path, |content| {
+            let mut file_hits = Vec::new();
+
+            for (index, line) in content.lines().enumerate() {
+                for captures in regex.captures_iter(line) {
+                    let whole = captures.get(0).map(|m| m.as_str()).unwrap_or("");
+                    if whole.is_empty() {
+                        continue;
+                    }
+
+                    let groups = if captures.len() > 1 {
+                        captures
+                            .iter()
+                            .skip(1)
+                            .map(|group| group.map(|m| m.as_str().to_string()).unwrap_or_default())
+                            .collect()
+                    } else {
+                        vec![whole.to_string()]
+                    };
+
+                    file_hits.push(ExtractHit { path: relative_path.clone(), line: index + 1, groups });
+                }
+            }
+
+            file_hits
+        })?;
+
+        hits.extend(file_hits);
+    }
+
+    Ok(hits)
+}
+
+#[tauri::command]
+pub async fn extract_matches(app_handle: tauri::AppHandle, pattern: String, scope: Option<String>) -> Result<Vec<ExtractHit>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let scope_path = resolve_scope(base_path, scope)?;
+    let regex = compile_search_pattern(&pattern)?;
+
+    timed!("extract_matches", scope_path.display(), {
+        extract_from_files(base_path, &scope_path, &regex)
+    })
+}
+
+// Non-overlapping occurrences of `term` in each note that contains it, most
+// occurrences first.
+fn term_counts(base_path: &Path, term: &str, case_sensitive: bool) -> Result<Vec<TermCount>, String> {
+    let term = if case_sensitive { term.to_string() } else { term.to_lowercase() };
+    let mut counts = Vec::new();
+
+    for path in collect_markdown_files(base_path)? {
+        let count = with_note_content(&path, |content| {
+            if case_sensitive {
+                content.matches(term.as_str()).count()
+            } else {
+                content.to_lowercase().matches(term.as_str()).count()
+            }
+        })?;
+
+        if count > 0 {
+            counts.push(TermCount { path: relative_path_string(base_path, &path)?, count });
+        }
+    }
+
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.path.cmp(&b.path)));
+    Ok(counts)
+}
+
+#[tauri::command]
+pub async fn count_term(app_handle: tauri::AppHandle, term: String, case_sensitive: bool) -> Result<Vec<TermCount>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    if term.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    timed!("count_term", base_path.display(), {
+        term_counts(base_path, &term, case_sensitive)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    fn search(base_path: &Path, query: &str, scope: Option<&str>) -> Result<Vec<String>, String> {
+        let scope_path = resolve_scope(base_path, scope.map(str::to_string))?;
+        let query_lower = query.to_lowercase();
+        let hits = search_files(base_path, &scope_path, |line| find_matches(line, &query_lower))?;
+        Ok(hits.into_iter().map(|hit| hit.path).collect())
+    }
+
+    #[test]
+    fn multibyte_matches_land_on_char_boundaries() {
+        let line = "café CAFÉ — Café";
+        let matches = find_matches(line, "café");
+
+        assert_eq!(matches, vec![(0, 5), (6, 11), (16, 21)]);
+        for &(start, end) in &matches {
+            assert!(line.is_char_boundary(start) && line.is_char_boundary(end));
+            assert_eq!(line[start..end].to_lowercase(), "café");
+        }
+
+        // The Kelvin sign lowercases to a one-byte `k` but spans three bytes
+        assert_eq!(find_matches("\u{212A} ok", "k"), vec![(0, 3), (5, 6)]);
+    }
+
+    #[test]
+    fn hits_report_every_match_on_the_line() {
+        let vault = TempVault::new();
+        vault.write("a.md", "plain line\nNeedle, needle and NEEDLE\n");
+
+        let hits = search_files(vault.path(), vault.path(), |line| find_matches(line, "needle")).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].line, 2);
+        assert_eq!((hits[0].match_start, hits[0].match_end), (0, 6));
+        assert_eq!(hits[0].matches, vec![(0, 6), (8, 14), (19, 25)]);
+    }
+
+    #[test]
+    fn scope_excludes_hits_outside_subtree() {
+        let vault = TempVault::new();
+        vault.write("root.md", "needle at the root");
+        vault.write("Projects/ClientX/brief.md", "needle in the brief");
+        vault.write("Projects/ClientX/Sub/deep.md", "deeper NEEDLE");
+        vault.write("Projects/ClientY/other.md", "needle elsewhere");
+        vault.write("Projects/ClientXYZ/prefix.md", "needle in a look-alike folder");
+
+        let mut scoped = search(vault.path(), "needle", Some("Projects/ClientX")).unwrap();
+        scoped.sort();
+        assert_eq!(scoped, vec!["Projects/ClientX/Sub/deep.md", "Projects/ClientX/brief.md"]);
+
+        assert_eq!(search(vault.path(), "needle", None).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn scope_must_be_an_existing_folder_in_the_vault() {
+        let vault = TempVault::new();
+        vault.write("Projects/brief.md", "needle");
+
+        assert!(search(vault.path(), "needle", Some("Projects/brief.md")).is_err());
+        assert!(search(vault.path(), "needle", Some("Missing")).is_err());
+        assert!(search(vault.path(), "needle", Some("../outside")).is_err());
+    }
+
+    fn regex_hits(base_path: &Path, pattern: &str) -> Vec<(usize, Vec<(usize, usize)>)> {
+        let regex = compile_search_pattern(pattern).unwrap();
+        search_files(base_path, base_path, |line| regex_matches(&regex, line))
+            .unwrap()
+            .into_iter()
+            .map(|hit| (hit.line, hit.matches))
+            .collect()
+    }
+
+    #[test]
+    fn regex_anchors_apply_per_line_and_never_span_lines() {
+        let vault = TempVault::new();
+        vault.write("a.md", "TODO first\nnot a TODO\n  TODO indented\n");
+
+        assert_eq!(regex_hits(vault.path(), "^TODO"), vec![(1, vec![(0, 4)])]);
+        assert_eq!(regex_hits(vault.path(), "TODO$"), vec![(2, vec![(6, 10)])]);
+        assert!(regex_hits(vault.path(), r"first\nnot").is_empty());
+        assert!(regex_hits(vault.path(), "(?s)first.not").is_empty());
+        assert!(regex_hits(vault.path(), "^").is_empty());
+    }
+
+    #[test]
+    fn regex_character_classes_report_each_match() {
+        let vault = TempVault::new();
+        vault.write("a.md", "due 2024-06 and 2025-01\nFIXME: 12-3\n");
+
+        assert_eq!(regex_hits(vault.path(), "[0-9]{4}-[0-9]{2}"), vec![(1, vec![(4, 11), (16, 23)])]);
+        assert_eq!(regex_hits(vault.path(), r"\bTODO\b|[A-Z]{5}"), vec![(2, vec![(0, 5)])]);
+        assert!(compile_search_pattern("[a-").is_err());
+    }
+
+    #[test]
+    fn extracts_two_capture_groups_across_files() {
+        let vault = TempVault::new();
+        vault.write("a.md", "Item: rent Amount: $1200\nnothing here\nItem: food Amount: $300");
+        vault.write("b/c.md", b"caf\xe9\nItem: travel Amount: $75");
+        vault.write("d.md", "Item: gift with no amount");
+
+        let regex = compile_search_pattern(r"Item: (\w+) Amount: \$(\d+)").unwrap();
+        let mut hits = extract_from_files(vault.path(), vault.path(), &regex).unwrap();
+        hits.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+
+        let found: Vec<(&str, usize, Vec<String>)> = hits
+            .iter()
+            .map(|hit| (hit.path.as_str(), hit.line, hit.groups.clone()))
+            .collect();
+        assert_eq!(found, vec![
+            ("a.md", 1, vec!["rent".to_string(), "1200".to_string()]),
+            ("a.md", 3, vec!["food".to_string(), "300".to_string()]),
+            ("b/c.md", 2, vec!["travel".to_string(), "75".to_string()]),
+        ]);
+    }
+
+    #[test]
+    fn counts_term_per_note_honouring_case() {
+        let vault = TempVault::new();
+        vault.write("three.md", "Rust, rust and RUST");
+        vault.write("one.md", "only one rust here");
+        vault.write("none.md", "nothing relevant");
+        vault.write("bad.md", b"caf\xe9 rust");
+
+        let counts = |case_sensitive| -> Vec<(String, usize)> {
+            term_counts(vault.path(), "rust", case_sensitive)
+                .unwrap()
+                .into_iter()
+                .map(|count| (count.path, count.count))
+                .collect()
+        };
+
+        assert_eq!(counts(false), vec![
+            ("three.md".to_string(), 3),
+            ("bad.md".to_string(), 1),
+            ("one.md".to_string(), 1),
+        ]);
+        assert_eq!(counts(true), vec![
+            ("bad.md".to_string(), 1),
+            ("one.md".to_string(), 1),
+            ("three.md".to_string(), 1),
+        ]);
+    }
+
+    #[test]
+    fn counts_are_non_overlapping() {
+        let vault = TempVault::new();
+        vault.write("a.md", "aaaa");
+
+        assert_eq!(term_counts(vault.path(), "aa", true).unwrap()[0].count, 2);
+    }
+
+    #[test]
+    fn invalid_utf8_note_does_not_fail_the_search() {
+        let vault = TempVault::new();
+        vault.write("bad.md", b"caf\xe9 needle");
+        vault.write("good.md", "needle");
+
+        let mut hits = search(vault.path(), "needle", None).unwrap();
+        hits.sort();
+        assert_eq!(hits, vec!["bad.md", "good.md"]);
+    }
+}
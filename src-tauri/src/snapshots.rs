@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::Manager;
+
+use crate::fs::{write_atomic, AppState, ILLEGAL_NAME_CHARS};
+use crate::hash::note_hashes;
+
+pub const SNAPSHOT_DIR: &str = ".tau_snapshots";
+
+// Content hash of every note at the moment the snapshot was taken.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Snapshot {
+    // Seconds since the Unix epoch
+    pub created: u64,
+    pub hashes: BTreeMap<String, String>,
+}
+
+// Notes that changed since a snapshot, each list sorted.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct SnapshotDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+fn snapshot_path(base_path: &Path, name: &str) -> Result<PathBuf, String> {
+    let name = name.trim();
+
+    if name.is_empty() || name == "." || name == ".." {
+        return Err("Snapshot name must not be empty".to_string());
+    }
+
+    if let Some(illegal) = name.chars().find(|c| ILLEGAL_NAME_CHARS.contains(c) || c.is_control()) {
+        return Err(format!("Snapshot name must not contain '{}'", illegal.escape_default()));
+    }
+
+    Ok(base_path.join(SNAPSHOT_DIR).join(format!("{}.json", name)))
+}
+
+fn diff_hashes(before: &BTreeMap<String, String>, after: &BTreeMap<String, String>) -> SnapshotDiff {
+    let mut diff = SnapshotDiff::default();
+
+    for (path, hash) in after {
+        match before.get(path) {
+            None => diff.added.push(path.clone()),
+            Some(old) if old != hash => diff.modified.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+
+    diff.removed = before
+        .keys()
+        .filter(|path| !after.contains_key(*path))
+        .cloned()
+        .collect();
+
+    diff
+}
+
+// Records every note's content hash under `name`, replacing an earlier
+// snapshot of the same name.
+#[tauri::command]
+pub async fn create_snapshot(app_handle: tauri::AppHandle, name: String) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    take_snapshot(base_path, &name)
+}
+
+fn take_snapshot(base_path: &Path, name: &str) -> Result<(), String> {
+    let path = snapshot_path(base_path, name)?;
+    let created = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let snapshot = Snapshot { created, hashes: note_hashes(base_path)? };
+
+    fs::create_dir_all(base_path.join(SNAPSHOT_DIR))
+        .map_err(|e| format!("Failed to create snapshot folder: {}", e))?;
+
+    let content = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+
+    write_atomic(&path, content.as_bytes())
+        .map_err(|e| format!("Failed to write snapshot: {}", e))
+}
+
+#[tauri::command]
+pub async fn diff_snapshot(app_handle: tauri::AppHandle, name: String) -> Result<SnapshotDiff, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    snapshot_diff(base_path, &name)
+}
+
+fn snapshot_diff(base_path: &Path, name: &str) -> Result<SnapshotDiff, String> {
+    let path = snapshot_path(base_path, name)?;
+
+    if !path.is_file() {
+        return Err(format!("Snapshot '{}' does not exist", name));
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read snapshot: {}", e))?;
+    let snapshot: Snapshot = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse snapshot: {}", e))?;
+
+    Ok(diff_hashes(&snapshot.hashes, &note_hashes(base_path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn one_modified_note_is_reported_exactly() {
+        let vault = TempVault::new();
+        vault.write("Kept.md", "unchanged");
+        vault.write("Edited.md", "before");
+        vault.write("Deleted.md", "gone soon");
+
+        take_snapshot(vault.path(), "morning").unwrap();
+        assert!(vault.path().join(SNAPSHOT_DIR).join("morning.json").is_file());
+
+        vault.write("Edited.md", "after");
+        let diff = snapshot_diff(vault.path(), "morning").unwrap();
+        assert_eq!(diff.modified, vec!["Edited.md"]);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+
+        fs::remove_file(vault.path().join("Deleted.md")).unwrap();
+        vault.write("New.md", "fresh");
+        let diff = snapshot_diff(vault.path(), "morning").unwrap();
+        assert_eq!(diff.added, vec!["New.md"]);
+        assert_eq!(diff.removed, vec!["Deleted.md"]);
+        assert_eq!(diff.modified, vec!["Edited.md"]);
+
+        assert!(snapshot_diff(vault.path(), "evening").is_err());
+    }
+}
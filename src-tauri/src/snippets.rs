@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::Manager;
+
+use crate::frontmatter::split_frontmatter;
+use crate::fs::{collect_markdown_files, relative_path_string, with_note_content, AppState};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CodeBlock {
+    pub path: String,
+    // First word of the info string; None for a bare fence
+    pub language: Option<String>,
+    // 1-based line of the opening fence
+    pub line: usize,
+    pub content: String,
+}
+
+// A code block whose closing fence hasn't been seen yet.
+struct OpenFence<'a> {
+    fence_char: char,
+    length: usize,
+    indent: usize,
+    language: Option<String>,
+    line: usize,
+    lines: Vec<&'a str>,
+}
+
+// An opening or closing fence: the fence character, its run length and
+// whatever follows the run.
+fn parse_fence(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start();
+    let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.chars().take_while(|c| *c == fence_char).count();
+
+    if length < 3 {
+        return None;
+    }
+
+    Some((fence_char, length, &trimmed[length..]))
+}
+
+// Fenced code blocks in document order. A block closes only on a fence of
+// the same character at least as long as the one that opened it, so a
+// ```` block can show ``` fences inside it. Opening fences may be indented
+// (inside list items); that indent is stripped from the content lines. An
+// unclosed block runs to the end of the note.
+pub(crate) fn parse_code_blocks(content: &str) -> Vec<(Option<String>, usize, String)> {
+    let (body, line_offset) = match split_frontmatter(content) {
+        Some((_, body)) => (body, content[..content.len() - body.len()].lines().count()),
+        None => (content, 0),
+    };
+
+    let mut blocks = Vec::new();
+    let mut open: Option<OpenFence> = None;
+
+    for (index, line) in body.lines().enumerate() {
+        let indent = line.len() - line.trim_start_matches(' ').len();
+
+        if let Some(fence) = open.as_mut() {
+            let closes = matches!(
+                parse_fence(line),
+                Some((c, length, rest)) if c == fence.fence_char && length >= fence.length && rest.trim().is_empty()
+            );
+            if closes {
+                blocks.push((fence.language.take(), fence.line, fence.lines.join("\n")));
+                open = None;
+            } else {
+                fence.lines.push(&line[indent.min(fence.indent)..]);
+            }
+            continue;
+        }
+
+        if let Some((fence_char, length, info)) = parse_fence(line) {
+            // Backtick info strings can't contain backticks (that's inline code)
+            if fence_char == '`' && info.contains('`') {
+                continue;
+            }
+            let language = info.split_whitespace().next().map(|word| word.to_string());
+            open = Some(OpenFence { fence_char, length, indent, language, line: line_offset + index + 1, lines: Vec::new() });
+        }
+    }
+
+    if let Some(fence) = open {
+        blocks.push((fence.language, fence.line, fence.lines.join("\n")));
+    }
+
+    blocks
+}
+
+// Every fenced code block in the vault, by path and then position. With
+// `language`, only blocks tagged with it (case-insensitively) are returned.
+#[tauri::command]
+pub async fn code_blocks(app_handle: tauri::AppHandle, language: Option<String>) -> Result<Vec<CodeBlock>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    collect_code_blocks(base_path, language.as_deref())
+}
+
+fn collect_code_blocks(base_path: &Path, language: Option<&str>) -> Result<Vec<CodeBlock>, String> {
+    let wanted = language.map(|language| language.trim().to_lowercase());
+    let mut blocks = Vec::new();
+
+    for path in collect_markdown_files(base_path)? {
+        let rel = relative_path_string(base_path, &path)?;
+
+        for (block_language, line, content) in with_note_content(&path, parse_code_blocks)? {
+            let matches = match (&wanted, &block_language) {
+                (None, _) => true,
+                (Some(wanted), Some(found)) => found.to_lowercase() == *wanted,
+                (Some(_), None) => false,
+            };
+            if matches {
+                blocks.push(CodeBlock { path: rel.clone(), language: block_language, line, content });
+            }
+        }
+    }
+
+    Ok(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn rust_blocks_are_extracted_and_others_filtered_out() {
+        let vault = TempVault::new();
+        vault.write(
+            "Snippets.md",
+            "---\ntitle: Snippets\n---\n```Rust\nfn main() {}\n```\n\n~~~python\nprint(1)\n~~~\n\n- item\n  ```rust ignore\n  let x = 1;\n  ```\n",
+        );
+        vault.write("Docs.md", "````markdown\n```rust\nnot a block\n```\n````\n```\nbare\n```");
+
+        let rust = collect_code_blocks(vault.path(), Some("rust")).unwrap();
+        let found: Vec<(&str, Option<&str>, usize, &str)> = rust
+            .iter()
+            .map(|block| (block.path.as_str(), block.language.as_deref(), block.line, block.content.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("Snippets.md", Some("Rust"), 4, "fn main() {}"), ("Snippets.md", Some("rust"), 13, "let x = 1;")]
+        );
+
+        // Without a filter, the nested fence stays inside its markdown block
+        let mut all = collect_code_blocks(vault.path(), None).unwrap();
+        all.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+        let languages: Vec<Option<&str>> = all.iter().map(|block| block.language.as_deref()).collect();
+        assert_eq!(languages, vec![Some("markdown"), None, Some("Rust"), Some("python"), Some("rust")]);
+        assert_eq!(all[0].content, "```rust\nnot a block\n```");
+    }
+}
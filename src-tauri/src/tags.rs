@@ -0,0 +1,355 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use tauri::Manager;
+
+use crate::frontmatter::{is_note_readonly, parse_frontmatter, readonly_error, split_frontmatter, with_frontmatter, Frontmatter};
+use crate::fs::{collect_markdown_files, relative_path_string, with_note_content, write_atomic, AppState, BulkFailure, BulkResult};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+pub(crate) fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').trim_end_matches('/');
+
+    // Purely numeric "tags" like #1 are issue references, not tags
+    if tag.is_empty() || tag.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(tag.to_lowercase())
+}
+
+fn frontmatter_tags(content: &str) -> Vec<String> {
+    let frontmatter = match parse_frontmatter(content) {
+        Some(frontmatter) => frontmatter,
+        None => return Vec::new(),
+    };
+
+    match frontmatter.get("tags") {
+        Some(serde_yaml::Value::Sequence(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str())
+            .filter_map(normalize_tag)
+            .collect(),
+        Some(serde_yaml::Value::String(tags)) => tags
+            .split([',', ' '])
+            .filter_map(normalize_tag)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// Inline `#tags` in the note body, ignoring fenced and inline code.
+fn inline_tags(body: &str) -> Vec<String> {
+    let tag_regex = Regex::new(r"(?:^|\s)#([\p{L}\p{N}_\-/]+)").unwrap();
+    let code_span_regex = Regex::new(r"`[^`]*`").unwrap();
+    let mut tags = Vec::new();
+    let mut in_fence = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let line = code_span_regex.replace_all(line, "");
+        for capture in tag_regex.captures_iter(&line) {
+            if let Some(tag) = normalize_tag(&capture[1]) {
+                tags.push(tag);
+            }
+        }
+    }
+
+    tags
+}
+
+// Distinct tags used by a note, from both frontmatter and the body.
+pub(crate) fn extract_tags(content: &str) -> BTreeSet<String> {
+    let body = split_frontmatter(content).map(|(_, body)| body).unwrap_or(content);
+
+    frontmatter_tags(content)
+        .into_iter()
+        .chain(inline_tags(body))
+        .collect()
+}
+
+// Vault-wide tag index: how many notes use each tag, most used first.
+pub(crate) fn collect_tag_counts(base_path: &Path) -> Result<Vec<TagCount>, String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for path in collect_markdown_files(base_path)? {
+        for tag in with_note_content(&path, extract_tags)? {
+            *counts.entry(tag).or_insert(0) += 1;
+        }
+    }
+
+    let mut tag_counts: Vec<TagCount> = counts
+        .into_iter()
+        .map(|(tag, count)| TagCount { tag, count })
+        .collect();
+    tag_counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+
+    Ok(tag_counts)
+}
+
+// Ranks how well `tag` matches a typed prefix. Matching the start of the tag
+// beats matching the start of a later word or nested segment; matches in
+// the middle of a word don't count at all.
+fn tag_match_rank(tag: &str, prefix: &str) -> Option<usize> {
+    if tag.starts_with(prefix) {
+        return Some(0);
+    }
+
+    tag.char_indices()
+        .filter(|(_, c)| matches!(c, '/' | '-' | '_'))
+        .any(|(i, c)| tag[i + c.len_utf8()..].starts_with(prefix))
+        .then_some(1)
+}
+
+#[tauri::command]
+pub async fn suggest_tags(app_handle: tauri::AppHandle, prefix: String, limit: usize) -> Result<Vec<TagCount>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let prefix = prefix.trim().trim_start_matches('#').to_lowercase();
+
+    let mut ranked: Vec<(usize, TagCount)> = collect_tag_counts(base_path)?
+        .into_iter()
+        .filter_map(|tag_count| tag_match_rank(&tag_count.tag, &prefix).map(|rank| (rank, tag_count)))
+        .collect();
+
+    // Stable sort keeps the frequency order within each rank
+    ranked.sort_by_key(|(rank, _)| *rank);
+
+    Ok(ranked.into_iter().take(limit).map(|(_, tag_count)| tag_count).collect())
+}
+
+// Applies `edit` to the raw frontmatter `tags` of a note, which are written
+// back as a YAML list. Returns None when `edit` reports no change.
+fn edit_frontmatter_tags<F>(content: &str, edit: F) -> Result<Option<String>, String>
+where
+    F: FnOnce(&mut Vec<String>) -> bool,
+{
+    let mut frontmatter = match parse_frontmatter(content) {
+        Some(frontmatter) => frontmatter,
+        None if split_frontmatter(content).is_some() => return Err("Frontmatter is not valid YAML".to_string()),
+        None => Frontmatter::new(),
+    };
+
+    let mut tags: Vec<String> = match frontmatter.get("tags") {
+        Some(serde_yaml::Value::Sequence(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str())
+            .map(|tag| tag.to_string())
+            .collect(),
+        Some(serde_yaml::Value::String(tags)) => tags
+            .split([',', ' '])
+            .filter(|tag| !tag.trim().is_empty())
+            .map(|tag| tag.trim().to_string())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    if !edit(&mut tags) {
+        return Ok(None);
+    }
+
+    if tags.is_empty() {
+        frontmatter.remove("tags");
+    } else {
+        let items = tags.into_iter().map(serde_yaml::Value::String).collect();
+        frontmatter.insert("tags".into(), serde_yaml::Value::Sequence(items));
+    }
+
+    with_frontmatter(content, &frontmatter).map(Some)
+}
+
+fn apply_to_notes<F>(base_path: &Path, rels: &[String], mut update: F) -> BulkResult
+where
+    F: FnMut(&str) -> Result<Option<String>, String>,
+{
+    let mut result = BulkResult::default();
+
+    for rel in rels {
+        let file_path = base_path.join(rel);
+
+        let outcome = if !file_path.is_file() {
+            Err(format!("File '{}' does not exist", rel))
+        } else if is_note_readonly(&file_path) {
+            Err(readonly_error(rel))
+        } else {
+            fs::read_to_string(&file_path)
+                .map_err(|e| format!("Failed to read note: {}", e))
+                .and_then(|content| update(&content))
+                .and_then(|updated| match updated {
+                    Some(updated) => write_atomic(&file_path, updated.as_bytes())
+                        .map(|_| true)
+                        .map_err(|e| format!("Failed to write note: {}", e)),
+                    None => Ok(false),
+                })
+        };
+
+        match outcome {
+            Ok(true) => result.succeeded.push(rel.clone()),
+            Ok(false) => result.skipped.push(rel.clone()),
+            Err(error) => result.failed.push(BulkFailure { path: rel.clone(), error }),
+        }
+    }
+
+    result
+}
+
+// Notes that already carry the tag, in frontmatter or inline, are skipped.
+#[tauri::command]
+pub async fn add_tag_to_notes(app_handle: tauri::AppHandle, rels: Vec<String>, tag: String) -> Result<BulkResult, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    add_tag(base_path, &rels, &tag)
+}
+
+fn add_tag(base_path: &Path, rels: &[String], tag: &str) -> Result<BulkResult, String> {
+    let tag = normalize_tag(tag).ok_or_else(|| format!("Invalid tag '{}'", tag))?;
+
+    Ok(apply_to_notes(base_path, rels, |content| {
+        if extract_tags(content).contains(&tag) {
+            return Ok(None);
+        }
+        edit_frontmatter_tags(content, |tags| {
+            tags.push(tag.clone());
+            true
+        })
+    }))
+}
+
+// Only frontmatter tags are removed; inline `#tags` in the body are left
+// for the user to edit.
+#[tauri::command]
+pub async fn remove_tag_from_notes(app_handle: tauri::AppHandle, rels: Vec<String>, tag: String) -> Result<BulkResult, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let tag = normalize_tag(&tag).ok_or_else(|| format!("Invalid tag '{}'", tag))?;
+
+    Ok(apply_to_notes(base_path, &rels, |content| {
+        edit_frontmatter_tags(content, |tags| {
+            let before = tags.len();
+            tags.retain(|existing| normalize_tag(existing).as_ref() != Some(&tag));
+            tags.len() != before
+        })
+    }))
+}
+
+// Notes with neither frontmatter tags nor inline hashtags. An empty `tags: []`
+// leaves a note untagged.
+#[tauri::command]
+pub async fn untagged_notes(app_handle: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    find_untagged(base_path)
+}
+
+fn find_untagged(base_path: &Path) -> Result<Vec<String>, String> {
+    let mut untagged = Vec::new();
+
+    for path in collect_markdown_files(base_path)? {
+        if with_note_content(&path, |content| extract_tags(content).is_empty())? {
+            untagged.push(relative_path_string(base_path, &path)?);
+        }
+    }
+
+    Ok(untagged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn unrelated_tags_do_not_match_the_prefix() {
+        assert_eq!(tag_match_rank("project", "proj"), Some(0));
+        assert_eq!(tag_match_rank("proj/alpha", "proj/"), Some(0));
+        assert_eq!(tag_match_rank("work/project", "proj"), Some(1));
+        assert_eq!(tag_match_rank("side-project", "proj"), Some(1));
+
+        assert_eq!(tag_match_rank("improject", "proj"), None);
+        assert_eq!(tag_match_rank("reading", "proj"), None);
+        assert_eq!(tag_match_rank("project", "proj/"), None);
+    }
+
+    #[test]
+    fn tag_counts_include_undecodable_notes() {
+        let vault = TempVault::new();
+        vault.write("a.md", "#project #reading");
+        vault.write("b.md", "---\ntags: [project]\n---\n");
+        vault.write("latin1.md", b"caf\xe9 #project");
+
+        let counts = collect_tag_counts(vault.path()).unwrap();
+        let counts: Vec<(&str, usize)> = counts.iter().map(|c| (c.tag.as_str(), c.count)).collect();
+        assert_eq!(counts, vec![("project", 3), ("reading", 1)]);
+    }
+
+    #[test]
+    fn adding_a_tag_creates_frontmatter_when_missing() {
+        let vault = TempVault::new();
+        vault.write("Bare.md", "# Title\n\nBody text.");
+        vault.write("Inline.md", "Already #project");
+
+        let rels = vec!["Bare.md".to_string(), "Inline.md".to_string(), "Missing.md".to_string()];
+        let result = add_tag(vault.path(), &rels, "#Project").unwrap();
+
+        assert_eq!(result.succeeded, vec!["Bare.md"]);
+        assert_eq!(result.skipped, vec!["Inline.md"]);
+        assert_eq!(result.failed.len(), 1);
+
+        let content = vault.read("Bare.md");
+        assert!(content.ends_with("---\n# Title\n\nBody text."), "{}", content);
+        assert!(extract_tags(&content).contains("project"));
+    }
+
+    #[test]
+    fn inline_tags_count_but_an_empty_tag_list_does_not() {
+        let vault = TempVault::new();
+        vault.write("Inline.md", "Some text #idea");
+        vault.write("Frontmatter.md", "---\ntags: [reading]\n---\n");
+        vault.write("Empty.md", "---\ntags: []\n---\nNothing here");
+        vault.write("Bare.md", "No tags at all, not even # this");
+
+        let mut untagged = find_untagged(vault.path()).unwrap();
+        untagged.sort();
+
+        assert_eq!(untagged, vec!["Bare.md", "Empty.md"]);
+    }
+}
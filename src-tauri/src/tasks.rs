@@ -0,0 +1,111 @@
+use regex::Regex;
+use std::fs;
+use tauri::Manager;
+
+use crate::frontmatter::{is_note_readonly, readonly_error};
+use crate::fs::{write_atomic, AppState};
+
+// `- [ ] text`, `* [x] text`, `1. [ ] text`, at any indentation
+const TASK_PATTERN: &str = r"^(\s*(?:[-*+]|\d+[.)])\s+\[)([ xX])(\].*)$";
+
+// Flips the first checkbox whose trimmed text equals `task_text`. Returns
+// the rewritten content and the new done state, or None if nothing matched.
+fn toggle_task_in_content(content: &str, task_text: &str) -> Option<(String, bool)> {
+    let task_regex = Regex::new(TASK_PATTERN).unwrap();
+    let wanted = task_text.trim();
+    let mut output = String::with_capacity(content.len());
+    let mut toggled = None;
+    let mut in_fence = false;
+
+    for line in content.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\n', '\r']);
+        let ending = &line[body.len()..];
+
+        let trimmed = body.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+
+        if toggled.is_none() && !in_fence {
+            if let Some(captures) = task_regex.captures(body) {
+                let rest = &captures[3][1..];
+                if rest.trim() == wanted {
+                    let done = &captures[2] == " ";
+                    output.push_str(&captures[1]);
+                    output.push(if done { 'x' } else { ' ' });
+                    output.push_str(&captures[3]);
+                    output.push_str(ending);
+                    toggled = Some(done);
+                    continue;
+                }
+            }
+        }
+
+        output.push_str(line);
+    }
+
+    toggled.map(|done| (output, done))
+}
+
+// If several tasks share the same text, only the first one is toggled.
+#[tauri::command]
+pub async fn toggle_task_by_text(app_handle: tauri::AppHandle, rel: String, task_text: String) -> Result<bool, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let file_path = base_path.join(&rel);
+
+    if !file_path.exists() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    if is_note_readonly(&file_path) {
+        return Err(readonly_error(&rel));
+    }
+
+    let content = fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read note: {}", e))?;
+
+    let (updated, done) = toggle_task_in_content(&content, &task_text)
+        .ok_or_else(|| format!("No task matching '{}' in '{}'", task_text.trim(), rel))?;
+
+    write_atomic(&file_path, updated.as_bytes())
+        .map_err(|e| format!("Failed to write note: {}", e))?;
+
+    Ok(done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggles_indented_and_nested_tasks() {
+        let content = "- [ ] parent\n  - [ ] child\n    1. [x] grandchild\n\t* [ ] tabbed\r\n";
+
+        let (updated, done) = toggle_task_in_content(content, "  child ").unwrap();
+        assert!(done);
+        assert_eq!(updated, "- [ ] parent\n  - [x] child\n    1. [x] grandchild\n\t* [ ] tabbed\r\n");
+
+        let (updated, done) = toggle_task_in_content(&updated, "grandchild").unwrap();
+        assert!(!done);
+        assert_eq!(updated, "- [ ] parent\n  - [x] child\n    1. [ ] grandchild\n\t* [ ] tabbed\r\n");
+
+        let (updated, _) = toggle_task_in_content(&updated, "tabbed").unwrap();
+        assert!(updated.ends_with("\t* [x] tabbed\r\n"));
+    }
+
+    #[test]
+    fn only_the_first_match_outside_fences_is_toggled() {
+        let content = "```\n- [ ] same\n```\n- [ ] same\n- [ ] same\n";
+
+        let (updated, _) = toggle_task_in_content(content, "same").unwrap();
+        assert_eq!(updated, "```\n- [ ] same\n```\n- [x] same\n- [ ] same\n");
+        assert!(toggle_task_in_content(content, "missing").is_none());
+    }
+}
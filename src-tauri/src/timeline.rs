@@ -0,0 +1,254 @@
+use chrono::{Days, Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::Manager;
+
+use crate::fs::{collect_note_entries, AppState, Entry};
+
+// Longest window `activity_by_day` will bucket, about ten years.
+const MAX_ACTIVITY_DAYS: u64 = 3660;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DayActivity {
+    // Local date as YYYY-MM-DD
+    pub date: String,
+    pub created: usize,
+    pub modified: usize,
+}
+
+// Creation time in seconds, falling back to mtime where the platform doesn't
+// record birth times.
+pub(crate) fn created_secs(entry: &Entry) -> Option<i64> {
+    entry
+        .created
+        .as_deref()
+        .unwrap_or(&entry.modified)
+        .parse()
+        .ok()
+}
+
+fn local_date<Tz: TimeZone>(secs: i64, tz: &Tz) -> Option<NaiveDate> {
+    tz.timestamp_opt(secs, 0).single().map(|time| time.date_naive())
+}
+
+fn created_on<Tz: TimeZone>(entries: Vec<Entry>, date: NaiveDate, tz: &Tz) -> Vec<Entry> {
+    let mut matching: Vec<Entry> = entries
+        .into_iter()
+        .filter(|entry| created_secs(entry).and_then(|secs| local_date(secs, tz)) == Some(date))
+        .collect();
+
+    matching.sort_by_key(created_secs);
+    matching
+}
+
+fn modified_between(entries: Vec<Entry>, start_secs: u64, end_secs: u64) -> Vec<Entry> {
+    let modified_secs = |entry: &Entry| entry.modified.parse::<u64>().ok();
+
+    let mut matching: Vec<Entry> = entries
+        .into_iter()
+        .filter(|entry| modified_secs(entry).is_some_and(|secs| (start_secs..=end_secs).contains(&secs)))
+        .collect();
+
+    matching.sort_by(|a, b| modified_secs(b).cmp(&modified_secs(a)).then_with(|| a.path.cmp(&b.path)));
+    matching
+}
+
+// Newest first by creation time. Entries without a birth time (`created` is
+// None) are placed by their mtime instead; the None tells the caller so.
+pub(crate) fn newest_created(mut entries: Vec<Entry>, limit: usize) -> Vec<Entry> {
+    entries.sort_by(|a, b| created_secs(b).cmp(&created_secs(a)).then_with(|| a.path.cmp(&b.path)));
+    entries.truncate(limit);
+    entries
+}
+
+// One bucket per day for the `days` days ending on `today`, oldest first.
+// Each note counts once for the day it was created (by mtime where there is
+// no birth time) and once for the day it was last modified.
+fn activity_buckets<Tz: TimeZone>(entries: &[Entry], today: NaiveDate, days: u64, tz: &Tz) -> Vec<DayActivity> {
+    let mut created: HashMap<NaiveDate, usize> = HashMap::new();
+    let mut modified: HashMap<NaiveDate, usize> = HashMap::new();
+
+    for entry in entries {
+        if let Some(day) = created_secs(entry).and_then(|secs| local_date(secs, tz)) {
+            *created.entry(day).or_insert(0) += 1;
+        }
+        if let Some(day) = entry.modified.parse().ok().and_then(|secs| local_date(secs, tz)) {
+            *modified.entry(day).or_insert(0) += 1;
+        }
+    }
+
+    (0..days)
+        .rev()
+        .filter_map(|back| today.checked_sub_days(Days::new(back)))
+        .map(|day| DayActivity {
+            date: day.format("%Y-%m-%d").to_string(),
+            created: created.get(&day).copied().unwrap_or(0),
+            modified: modified.get(&day).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn notes_created_on(app_handle: tauri::AppHandle, date: String) -> Result<Vec<Entry>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let day = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}', expected YYYY-MM-DD", date))?;
+
+    Ok(created_on(collect_note_entries(base_path)?, day, &Local))
+}
+
+// Notes whose mtime falls within `[start_secs, end_secs]`, both ends
+// inclusive, most recently modified first.
+#[tauri::command]
+pub async fn entries_modified_between(app_handle: tauri::AppHandle, start_secs: u64, end_secs: u64) -> Result<Vec<Entry>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    if start_secs > end_secs {
+        return Err(format!("Start {} is after end {}", start_secs, end_secs));
+    }
+
+    Ok(modified_between(collect_note_entries(base_path)?, start_secs, end_secs))
+}
+
+#[tauri::command]
+pub async fn recently_created(app_handle: tauri::AppHandle, limit: usize) -> Result<Vec<Entry>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    Ok(newest_created(collect_note_entries(base_path)?, limit))
+}
+
+// Created/modified counts for each of the last `days` days, today included,
+// for the activity heatmap.
+#[tauri::command]
+pub async fn activity_by_day(app_handle: tauri::AppHandle, days: u64) -> Result<Vec<DayActivity>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    if days > MAX_ACTIVITY_DAYS {
+        return Err(format!("At most {} days of activity can be shown", MAX_ACTIVITY_DAYS));
+    }
+
+    let entries = collect_note_entries(base_path)?;
+    Ok(activity_buckets(&entries, Local::now().date_naive(), days, &Local))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+
+    fn entry(path: &str, created: Option<i64>, modified: i64) -> Entry {
+        Entry {
+            name: path.to_string(),
+            path: path.to_string(),
+            is_dir: false,
+            modified: modified.to_string(),
+            created: created.map(|secs| secs.to_string()),
+            size: 0,
+        }
+    }
+
+    fn paths(entries: &[Entry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.path.as_str()).collect()
+    }
+
+    #[test]
+    fn created_on_respects_local_midnight() {
+        let tz = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+        let midnight = tz.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap().timestamp();
+        let day = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+
+        let entries = || vec![
+            entry("before.md", Some(midnight - 1), 0),
+            entry("at.md", Some(midnight), 0),
+            entry("late.md", Some(midnight + 86_399), 0),
+            entry("next.md", Some(midnight + 86_400), 0),
+        ];
+
+        assert_eq!(paths(&created_on(entries(), day, &tz)), vec!["at.md", "late.md"]);
+        // In UTC the same instants straddle a different midnight
+        assert_eq!(paths(&created_on(entries(), day, &Utc)), vec!["late.md", "next.md"]);
+    }
+
+    #[test]
+    fn created_on_falls_back_to_mtime() {
+        let day = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let noon = Utc.with_ymd_and_hms(2024, 3, 10, 12, 0, 0).unwrap().timestamp();
+
+        let entries = vec![entry("no-birth.md", None, noon), entry("other.md", Some(0), noon)];
+        assert_eq!(paths(&created_on(entries, day, &Utc)), vec!["no-birth.md"]);
+    }
+
+    #[test]
+    fn newest_created_uses_mtime_without_birth_time() {
+        let entries = vec![
+            entry("old.md", Some(100), 900),
+            entry("no-birth-new.md", None, 500),
+            entry("mid.md", Some(300), 300),
+            entry("no-birth-old.md", None, 50),
+        ];
+
+        let newest = newest_created(entries, 3);
+        assert_eq!(paths(&newest), vec!["no-birth-new.md", "mid.md", "old.md"]);
+        assert!(newest[0].created.is_none());
+    }
+
+    #[test]
+    fn note_modified_today_lands_in_todays_bucket() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 9, 0, 0).unwrap().timestamp();
+        let three_days_ago = now - 3 * 86_400;
+
+        let entries = vec![
+            entry("fresh.md", Some(now), now),
+            entry("edited.md", Some(three_days_ago), now),
+            entry("stale.md", Some(three_days_ago), three_days_ago),
+        ];
+        let buckets = activity_buckets(&entries, today, 7, &Utc);
+
+        assert_eq!(buckets.len(), 7);
+        assert_eq!(buckets[0].date, "2024-03-04");
+        assert_eq!(buckets[6].date, "2024-03-10");
+        assert_eq!((buckets[6].created, buckets[6].modified), (1, 2));
+        assert_eq!((buckets[3].created, buckets[3].modified), (2, 1));
+        assert!(activity_buckets(&entries, today, 0, &Utc).is_empty());
+    }
+
+    #[test]
+    fn modified_between_includes_both_ends() {
+        let entries = vec![
+            entry("before.md", None, 99),
+            entry("start.md", None, 100),
+            entry("middle.md", None, 150),
+            entry("end.md", None, 200),
+            entry("after.md", None, 201),
+        ];
+
+        assert_eq!(paths(&modified_between(entries, 100, 200)), vec!["end.md", "middle.md", "start.md"]);
+        assert_eq!(paths(&modified_between(vec![entry("exact.md", None, 150)], 150, 150)), vec!["exact.md"]);
+    }
+}
@@ -0,0 +1,43 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+// Env var that turns on command logging in release builds, where the log
+// plugin is otherwise not installed.
+pub(crate) const LOG_ENV_VAR: &str = "TAU_LOG";
+
+pub(crate) fn logging_enabled() -> bool {
+    cfg!(debug_assertions) || std::env::var_os(LOG_ENV_VAR).is_some()
+}
+
+// One `key=value` line per command so slow operations can be grepped out of
+// the log plugin's output.
+pub(crate) fn log_command<T>(command: &str, path: &dyn Display, result: &Result<T, String>, elapsed: Duration) {
+    let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+
+    match result {
+        Ok(_) => log::info!(
+            target: "tau::command",
+            "command={} path={:?} outcome=ok elapsed_ms={:.1}",
+            command, path.to_string(), elapsed_ms
+        ),
+        Err(error) => log::warn!(
+            target: "tau::command",
+            "command={} path={:?} outcome=error error={:?} elapsed_ms={:.1}",
+            command, path.to_string(), error, elapsed_ms
+        ),
+    }
+}
+
+// Runs `$body` (which may use `?`) and logs the command name, path, outcome
+// and elapsed time before handing back its result.
+macro_rules! timed {
+    ($command:expr, $path:expr, $body:block) => {{
+        let started = std::time::Instant::now();
+        let body = || $body;
+        let result = body();
+        $crate::timing::log_command($command, &$path, &result, started.elapsed());
+        result
+    }};
+}
+
+pub(crate) use timed;
@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::Path;
+use tauri::Manager;
+
+use crate::fs::{relative_path_string, sanitize_relative_path, unique_destination, AppState};
+use crate::outline::forget_outlines;
+
+// Soft-deleted entries live here, mirroring their original location.
+pub(crate) const TRASH_DIR: &str = ".trash";
+
+// Moves `rel` into the trash at the same relative path, numbering the name
+// if something already sits there. Returns the trashed path (relative to the
+// vault).
+pub(crate) fn move_to_trash(base_path: &Path, rel: &Path) -> Result<String, String> {
+    let source = base_path.join(rel);
+    let file_name = rel
+        .file_name()
+        .ok_or("Failed to get file name")?
+        .to_string_lossy()
+        .to_string();
+
+    let trash_parent = base_path.join(TRASH_DIR).join(rel.parent().unwrap_or(Path::new("")));
+    fs::create_dir_all(&trash_parent)
+        .map_err(|e| format!("Failed to create trash folder: {}", e))?;
+
+    let destination = unique_destination(&trash_parent, &file_name);
+    fs::rename(&source, &destination)
+        .map_err(|e| format!("Failed to move '{}' to trash: {}", file_name, e))?;
+
+    relative_path_string(base_path, &destination)
+}
+
+// Moves a trashed entry back to the path it mirrors, recreating missing
+// parent folders. `trash_rel` is relative to the trash, with or without a
+// leading `.trash/`. Folders come back whole, subfolders and order files
+// included. Returns the restored vault-relative path.
+pub(crate) fn restore_entry(base_path: &Path, trash_rel: &str) -> Result<String, String> {
+    let rel = sanitize_relative_path(trash_rel)?;
+    let rel = rel.strip_prefix(TRASH_DIR).unwrap_or(&rel);
+
+    if rel.as_os_str().is_empty() {
+        return Err("Nothing to restore".to_string());
+    }
+
+    let trash_root = base_path.join(TRASH_DIR);
+    let source = trash_root.join(rel);
+    let destination = base_path.join(rel);
+
+    if !source.exists() {
+        return Err(format!("'{}' is not in the trash", trash_rel));
+    }
+
+    if destination.exists() {
+        return Err(format!("Destination path '{}' already exists", rel.display()));
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create parent directories: {}", e))?;
+    }
+
+    fs::rename(&source, &destination)
+        .map_err(|e| format!("Failed to restore '{}': {}", trash_rel, e))?;
+
+    // Drop the trash folders that only existed to mirror the restored path
+    let mut parent = source.parent();
+    while let Some(dir) = parent {
+        if dir == trash_root || fs::remove_dir(dir).is_err() {
+            break;
+        }
+        parent = dir.parent();
+    }
+
+    relative_path_string(base_path, &destination)
+}
+
+#[tauri::command]
+pub async fn restore_from_trash(app_handle: tauri::AppHandle, trash_rel: String) -> Result<String, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let mut state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path.clone(),
+        None => return Err("No vault set".to_string()),
+    };
+
+    // Whatever was cached for the restored path belonged to an earlier note
+    let restored = restore_entry(&base_path, &trash_rel)?;
+    forget_outlines(&mut state_guard.outlines, &restored);
+
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ordering;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn folder_round_trips_through_trash() {
+        let vault = TempVault::new();
+        vault.write("Projects/Alpha/plan.md", "# Plan");
+        vault.write("Projects/Alpha/Deep/notes.md", "deep");
+        vault.write("Projects/Alpha/.tau_order.json", r#"{"order":["plan.md","Deep"]}"#);
+        vault.write("Projects/keep.md", "keep");
+
+        let trashed = move_to_trash(vault.path(), Path::new("Projects/Alpha")).unwrap();
+        assert_eq!(trashed, format!("{}/Projects/Alpha", TRASH_DIR));
+        assert!(!vault.path().join("Projects/Alpha").exists());
+        assert!(vault.path().join(".trash/Projects/Alpha/Deep/notes.md").is_file());
+
+        let restored = restore_entry(vault.path(), &trashed).unwrap();
+        assert_eq!(restored, "Projects/Alpha");
+        assert_eq!(fs::read_to_string(vault.path().join("Projects/Alpha/Deep/notes.md")).unwrap(), "deep");
+        assert_eq!(ordering::load(&vault.path().join("Projects/Alpha")).names(), vec!["plan.md", "Deep"]);
+
+        // Mirror folders are cleaned up, the trash root itself stays
+        assert!(!vault.path().join(".trash/Projects").exists());
+        assert!(vault.path().join(TRASH_DIR).is_dir());
+    }
+
+    #[test]
+    fn note_round_trips_and_refuses_to_overwrite() {
+        let vault = TempVault::new();
+        vault.write("Inbox/idea.md", "idea");
+
+        move_to_trash(vault.path(), Path::new("Inbox/idea.md")).unwrap();
+        assert!(!vault.path().join("Inbox/idea.md").exists());
+
+        vault.write("Inbox/idea.md", "new idea");
+        assert!(restore_entry(vault.path(), "Inbox/idea.md").is_err());
+
+        fs::remove_file(vault.path().join("Inbox/idea.md")).unwrap();
+        assert_eq!(restore_entry(vault.path(), "Inbox/idea.md").unwrap(), "Inbox/idea.md");
+        assert_eq!(fs::read_to_string(vault.path().join("Inbox/idea.md")).unwrap(), "idea");
+    }
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::fs::{sanitize_relative_path, AppState};
+
+// Sidebar state that should survive restarts, stored per vault.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct UiState {
+    #[serde(default)]
+    pub expanded_folders: Vec<String>,
+    // Folders pinned to the top of the sidebar, in the order they were pinned
+    #[serde(default)]
+    pub pinned_folders: Vec<String>,
+}
+
+fn get_ui_state_path(base_path: &Path) -> PathBuf {
+    base_path.join(".tau_ui_state.json")
+}
+
+pub(crate) fn read_ui_state(base_path: &Path) -> UiState {
+    fs::read_to_string(get_ui_state_path(base_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn write_ui_state(base_path: &Path, ui_state: &UiState) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(ui_state)
+        .map_err(|e| format!("Failed to serialize UI state: {}", e))?;
+
+    fs::write(get_ui_state_path(base_path), content)
+        .map_err(|e| format!("Failed to write UI state: {}", e))
+}
+
+// Whether `path` is `rel` itself or inside it.
+fn is_within(path: &str, rel: &str) -> bool {
+    let rel = rel.trim_end_matches('/');
+    path == rel || path.starts_with(&format!("{}/", rel))
+}
+
+// Unpins `rel` and any pinned folder beneath it, after a delete.
+pub(crate) fn unpin_removed(base_path: &Path, rel: &str) -> Result<(), String> {
+    let mut ui_state = read_ui_state(base_path);
+    let before = ui_state.pinned_folders.len();
+
+    ui_state.pinned_folders.retain(|folder| !is_within(folder, rel));
+
+    if ui_state.pinned_folders.len() == before {
+        return Ok(());
+    }
+    write_ui_state(base_path, &ui_state)
+}
+
+// Points pins at or under `from` at their new place under `to`.
+pub(crate) fn move_pins(base_path: &Path, from: &str, to: &str) -> Result<(), String> {
+    let mut ui_state = read_ui_state(base_path);
+    let mut changed = false;
+
+    for folder in ui_state.pinned_folders.iter_mut() {
+        if is_within(folder, from) {
+            *folder = format!("{}{}", to.trim_end_matches('/'), &folder[from.trim_end_matches('/').len()..]);
+            changed = true;
+        }
+    }
+
+    if !changed {
+        return Ok(());
+    }
+    write_ui_state(base_path, &ui_state)
+}
+
+// Folders deleted or renamed outside the app are dropped, and the pruned
+// list is saved back so stale paths don't accumulate.
+#[tauri::command]
+pub async fn get_expanded_folders(app_handle: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    load_expanded_folders(base_path)
+}
+
+fn load_expanded_folders(base_path: &Path) -> Result<Vec<String>, String> {
+    let mut ui_state = read_ui_state(base_path);
+    let before = ui_state.expanded_folders.len();
+
+    ui_state.expanded_folders.retain(|folder| {
+        sanitize_relative_path(folder)
+            .map(|rel| base_path.join(rel).is_dir())
+            .unwrap_or(false)
+    });
+
+    if ui_state.expanded_folders.len() != before {
+        write_ui_state(base_path, &ui_state)?;
+    }
+
+    Ok(ui_state.expanded_folders)
+}
+
+#[tauri::command]
+pub async fn set_expanded_folders(app_handle: tauri::AppHandle, paths: Vec<String>) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let mut ui_state = read_ui_state(base_path);
+
+    let mut folders: Vec<String> = Vec::new();
+    for path in paths {
+        sanitize_relative_path(&path)?;
+        if !folders.contains(&path) {
+            folders.push(path);
+        }
+    }
+    ui_state.expanded_folders = folders;
+
+    write_ui_state(base_path, &ui_state)
+}
+
+#[tauri::command]
+pub async fn pin_folder(app_handle: tauri::AppHandle, rel: String, pinned: bool) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let folder = sanitize_relative_path(&rel)?.to_string_lossy().replace('\\', "/");
+
+    if pinned && (folder.is_empty() || !base_path.join(&folder).is_dir()) {
+        return Err(format!("Folder '{}' does not exist", rel));
+    }
+
+    let mut ui_state = read_ui_state(base_path);
+    let already = ui_state.pinned_folders.contains(&folder);
+
+    if pinned && !already {
+        ui_state.pinned_folders.push(folder);
+    } else if !pinned && already {
+        ui_state.pinned_folders.retain(|pinned_folder| pinned_folder != &folder);
+    } else {
+        return Ok(());
+    }
+
+    write_ui_state(base_path, &ui_state)
+}
+
+// Like the expanded folders, pins whose folder vanished outside the app are
+// dropped and the list saved back.
+#[tauri::command]
+pub async fn get_pinned_folders(app_handle: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let mut ui_state = read_ui_state(base_path);
+    let before = ui_state.pinned_folders.len();
+
+    ui_state.pinned_folders.retain(|folder| base_path.join(folder).is_dir());
+
+    if ui_state.pinned_folders.len() != before {
+        write_ui_state(base_path, &ui_state)?;
+    }
+
+    Ok(ui_state.pinned_folders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempVault;
+
+    #[test]
+    fn deleted_folders_are_dropped_on_load() {
+        let vault = TempVault::new();
+        fs::create_dir_all(vault.path().join("Projects/Alpha")).unwrap();
+        fs::create_dir_all(vault.path().join("Archive")).unwrap();
+        let ui_state = UiState {
+            expanded_folders: vec!["Projects".into(), "Projects/Alpha".into(), "Archive".into()],
+            ..Default::default()
+        };
+        write_ui_state(vault.path(), &ui_state).unwrap();
+
+        fs::remove_dir_all(vault.path().join("Archive")).unwrap();
+
+        assert_eq!(load_expanded_folders(vault.path()).unwrap(), vec!["Projects", "Projects/Alpha"]);
+        // The pruned list is saved back
+        assert_eq!(read_ui_state(vault.path()).expanded_folders, vec!["Projects", "Projects/Alpha"]);
+    }
+
+    #[test]
+    fn deleting_a_pinned_folder_unpins_it_and_its_descendants() {
+        let vault = TempVault::new();
+        let ui_state = UiState {
+            pinned_folders: vec!["Projects".into(), "Projects/Alpha".into(), "Projects-old".into(), "Inbox".into()],
+            ..Default::default()
+        };
+        write_ui_state(vault.path(), &ui_state).unwrap();
+
+        unpin_removed(vault.path(), "Projects").unwrap();
+
+        // A sibling that only shares the prefix keeps its pin
+        assert_eq!(read_ui_state(vault.path()).pinned_folders, vec!["Projects-old", "Inbox"]);
+    }
+}
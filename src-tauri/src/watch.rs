@@ -0,0 +1,128 @@
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use tauri::{Emitter, Manager};
+
+use crate::fs::AppState;
+
+pub const NOTE_CHANGED_EVENT: &str = "note://changed";
+
+// The single note the editor has subscribed to. Replacing it drops the old
+// watcher, which unregisters it.
+#[derive(Default)]
+pub struct NoteWatcher {
+    watched: Option<(String, RecommendedWatcher)>,
+}
+
+// Whether `event` is a create, modify or remove touching `file_path`. Reads
+// and changes to other files in the same folder don't count.
+fn is_note_change(event: &notify::Event, file_path: &Path) -> bool {
+    let is_change = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_));
+    is_change && event.paths.iter().any(|path| path == file_path)
+}
+
+// Watches `rel` and emits NOTE_CHANGED_EVENT whenever it changes on disk.
+fn start_watch(app_handle: &tauri::AppHandle, base_path: &Path, rel: &str) -> Result<RecommendedWatcher, String> {
+    let file_path = base_path.join(rel);
+
+    if !file_path.is_file() {
+        return Err(format!("File '{}' does not exist", rel));
+    }
+
+    // Editors and sync tools often save by replacing the file, which ends a
+    // watch on the file itself, so the parent folder is watched instead and
+    // events for other files are filtered out
+    let parent = file_path.parent().ok_or("Failed to get parent directory")?.to_path_buf();
+
+    let emitter = app_handle.clone();
+    let event_rel = rel.to_string();
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        let event = match result {
+            Ok(event) => event,
+            Err(_) => return,
+        };
+
+        if is_note_change(&event, &file_path) {
+            let _ = emitter.emit(NOTE_CHANGED_EVENT, event_rel.clone());
+        }
+    })
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    watcher
+        .watch(&parent, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch note: {}", e))?;
+
+    Ok(watcher)
+}
+
+#[tauri::command]
+pub async fn watch_note(app_handle: tauri::AppHandle, rel: String) -> Result<(), String> {
+    let state = app_handle.state::<std::sync::Mutex<AppState>>();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+
+    let base_path = match &state_guard.vault_path {
+        Some(vault_path) => vault_path,
+        None => return Err("No vault set".to_string()),
+    };
+
+    let watcher = start_watch(&app_handle, base_path, &rel)?;
+
+    let note_watcher = app_handle.state::<std::sync::Mutex<NoteWatcher>>();
+    let mut watcher_guard = note_watcher.lock().map_err(|e| e.to_string())?;
+    watcher_guard.watched = Some((rel, watcher));
+
+    Ok(())
+}
+
+// Re-registers the current watch after the vault root came back: the old
+// watch died with the folder it was attached to. A note that didn't come
+// back ends the watch.
+pub(crate) fn rewatch(app_handle: &tauri::AppHandle, base_path: &Path) -> Result<(), String> {
+    let note_watcher = match app_handle.try_state::<std::sync::Mutex<NoteWatcher>>() {
+        Some(note_watcher) => note_watcher,
+        None => return Ok(()),
+    };
+    let mut watcher_guard = note_watcher.lock().map_err(|e| e.to_string())?;
+
+    if let Some((rel, _)) = watcher_guard.watched.take() {
+        if let Ok(watcher) = start_watch(app_handle, base_path, &rel) {
+            watcher_guard.watched = Some((rel, watcher));
+        }
+    }
+
+    Ok(())
+}
+
+// Only stops the watch if `rel` is the note currently being watched, so a
+// late unwatch for a previous note can't cancel the new one.
+#[tauri::command]
+pub async fn unwatch_note(app_handle: tauri::AppHandle, rel: String) -> Result<(), String> {
+    let note_watcher = app_handle.state::<std::sync::Mutex<NoteWatcher>>();
+    let mut watcher_guard = note_watcher.lock().map_err(|e| e.to_string())?;
+
+    if matches!(&watcher_guard.watched, Some((watched, _)) if *watched == rel) {
+        watcher_guard.watched = None;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, DataChange, ModifyKind};
+    use notify::Event;
+
+    #[test]
+    fn edits_to_other_notes_are_filtered_out() {
+        let watched = Path::new("/vault/Projects/Open.md");
+        let modify = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+
+        assert!(is_note_change(&Event::new(modify).add_path(watched.to_path_buf()), watched));
+        // Saving by replace shows up as a create of the same path
+        assert!(is_note_change(&Event::new(EventKind::Create(CreateKind::File)).add_path(watched.to_path_buf()), watched));
+
+        let sibling = Path::new("/vault/Projects/Other.md").to_path_buf();
+        assert!(!is_note_change(&Event::new(modify).add_path(sibling), watched));
+        assert!(!is_note_change(&Event::new(EventKind::Access(AccessKind::Any)).add_path(watched.to_path_buf()), watched));
+    }
+}